@@ -0,0 +1,19 @@
+use eyre::Result;
+
+use super::{ChatBackend, ChatMessage};
+
+/// Nobody can play through the console, anything the game says to chat is printed instead.
+/// Used when watching replays so nothing gets sent to a real chat.
+#[derive(Debug, Default)]
+pub struct ConsoleChatBackend;
+
+impl ChatBackend for ConsoleChatBackend {
+    fn receive(&mut self) -> Option<ChatMessage> {
+        None
+    }
+
+    fn send(&mut self, message: String) -> Result<()> {
+        println!("chat: {}", message);
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::irc_message::{escape_tag_value, IrcMessage};
+use crate::chatter::{Chatter, ChatterRole};
+
+/// A stand in for Twitch chat running on localhost. It speaks just enough of Twitch's IRC
+/// for the Twitch backend to log in, so tests can play the game through a real connection
+/// and check what the game said back to chat.
+///
+/// Every client that connects is served, and what chat says goes to all of them.
+pub struct FakeIrcServer {
+    address: SocketAddr,
+    channel: String,
+    send_to_client: Sender<String>,
+    receive_from_client: Receiver<String>,
+}
+
+/// Everyone connected to the server, and what chat said before anyone connected
+#[derive(Default)]
+struct Clients {
+    streams: Vec<Arc<Mutex<TcpStream>>>,
+    waiting: Vec<String>,
+}
+
+impl FakeIrcServer {
+    pub fn start(channel: &str) -> io::Result<FakeIrcServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let (send_to_client, outgoing) = mpsc::channel();
+        let (send_to_test, receive_from_client) = mpsc::channel();
+
+        thread::spawn(move || {
+            if let Err(error) = Self::serve(listener, outgoing, send_to_test) {
+                eprintln!("Fake IRC server stopped: {}", error);
+            }
+        });
+
+        Ok(FakeIrcServer {
+            address,
+            channel: format!("#{}", channel.to_lowercase()),
+            send_to_client,
+            receive_from_client,
+        })
+    }
+
+    /// Where to point the Twitch backend at, for example 127.0.0.1:34567
+    pub fn get_address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Pretend a viewer sent a message to the channel
+    pub fn send_chat_message(&self, chatter: &Chatter, message: &str) {
+        let nick = chatter.name.to_lowercase();
+        let (badges, is_moderator) = match chatter.role {
+            ChatterRole::Viewer => ("", false),
+            ChatterRole::Moderator => ("moderator/1", true),
+            ChatterRole::Broadcaster => ("broadcaster/1", false),
+        };
+        self.send_line(format!(
+            "@badges={};color=#{:02X}{:02X}{:02X};display-name={};mod={};subscriber={};user-id={};user-type= :{nick}!{nick}@{nick}.tmi.twitch.tv PRIVMSG {} :{}",
+            badges,
+            chatter.red,
+            chatter.green,
+            chatter.blue,
+            escape_tag_value(&chatter.name),
+            is_moderator as u8,
+            chatter.is_subscriber as u8,
+            escape_tag_value(&chatter.user_id),
+            self.channel,
+            message,
+            nick = nick
+        ));
+    }
+
+    /// Twitch pings every few minutes and closes the connection if there is no pong
+    pub fn ping(&self) {
+        self.send_line("PING :tmi.twitch.tv".to_owned());
+    }
+
+    /// The next raw line the client sent, like `NICK streamer`
+    pub fn next_line(&self, timeout: Duration) -> Option<String> {
+        self.receive_from_client.recv_timeout(timeout).ok()
+    }
+
+    /// The text of the next message the client sent to the channel, anything else it sends
+    /// before that is skipped over
+    pub fn next_chat_message(&self, timeout: Duration) -> Option<String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let line = self.next_line(deadline.saturating_duration_since(Instant::now()))?;
+            let irc_message = match IrcMessage::parse(&line) {
+                Some(irc_message) => irc_message,
+                None => continue,
+            };
+            if irc_message.command == "PRIVMSG" && irc_message.params.first() == Some(&self.channel)
+            {
+                return irc_message.get_text().map(|text| text.to_owned());
+            }
+        }
+    }
+
+    fn send_line(&self, line: String) {
+        // the server thread lives as long as the server does, so this only fails if it panicked
+        self.send_to_client.send(line).ok();
+    }
+
+    fn serve(
+        listener: TcpListener,
+        outgoing: Receiver<String>,
+        send_to_test: Sender<String>,
+    ) -> io::Result<()> {
+        let clients: Arc<Mutex<Clients>> = Arc::default();
+        let broadcast_clients = Arc::clone(&clients);
+        thread::spawn(move || {
+            for line in outgoing {
+                let mut clients = broadcast_clients.lock().unwrap();
+                if clients.streams.is_empty() {
+                    clients.waiting.push(line);
+                } else {
+                    clients
+                        .streams
+                        .retain(|client| Self::write_line(client, &line).is_ok());
+                }
+            }
+        });
+
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let writer = Arc::new(Mutex::new(stream.try_clone()?));
+            {
+                let mut clients = clients.lock().unwrap();
+                for line in clients.waiting.drain(..) {
+                    Self::write_line(&writer, &line)?;
+                }
+                clients.streams.push(Arc::clone(&writer));
+            }
+            let send_to_test = send_to_test.clone();
+            thread::spawn(move || {
+                if let Err(error) = Self::serve_client(stream, writer, send_to_test) {
+                    eprintln!("Fake IRC client dropped: {}", error);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn serve_client(
+        stream: TcpStream,
+        writer: Arc<Mutex<TcpStream>>,
+        send_to_test: Sender<String>,
+    ) -> io::Result<()> {
+        let mut nick = String::new();
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            if let Some(irc_message) = IrcMessage::parse(&line) {
+                let param = irc_message.params.first().cloned().unwrap_or_default();
+                let reply = match irc_message.command.as_str() {
+                    "CAP" => Some(format!(
+                        ":tmi.twitch.tv CAP * ACK :{}",
+                        irc_message.get_text().unwrap_or_default()
+                    )),
+                    "NICK" => {
+                        nick = param;
+                        Some(format!(":tmi.twitch.tv 001 {} :Welcome, GLHF!", nick))
+                    }
+                    "JOIN" => Some(format!(
+                        ":{nick}!{nick}@{nick}.tmi.twitch.tv JOIN {}",
+                        param,
+                        nick = nick
+                    )),
+                    _ => None,
+                };
+                if let Some(reply) = reply {
+                    Self::write_line(&writer, &reply)?;
+                }
+            }
+            if send_to_test.send(line).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lines go out in one write so replies and chat messages to the same client don't mix
+    fn write_line(client: &Mutex<TcpStream>, line: &str) -> io::Result<()> {
+        client
+            .lock()
+            .unwrap()
+            .write_all(format!("{}\r\n", line).as_bytes())
+    }
+}
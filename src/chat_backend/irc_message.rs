@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+/// A single line of IRC, with the extra tags Twitch puts in front of it
+///
+/// ```text
+/// @color=#FF0000;display-name=Alice;subscriber=1 :alice!alice@alice.tmi.twitch.tv PRIVMSG #channel :#fire 3
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrcMessage {
+    pub tags: HashMap<String, String>,
+    pub prefix: Option<String>,
+    pub command: String,
+    pub params: Vec<String>,
+}
+
+impl IrcMessage {
+    pub fn parse(line: &str) -> Option<IrcMessage> {
+        let mut rest = line.trim_end_matches(&['\r', '\n'][..]);
+
+        let mut tags = HashMap::new();
+        if let Some(tagged) = rest.strip_prefix('@') {
+            let mut parts = tagged.splitn(2, ' ');
+            for tag in parts.next()?.split(';') {
+                let mut tag = tag.splitn(2, '=');
+                let key = tag.next()?.to_owned();
+                let value = unescape_tag_value(tag.next().unwrap_or_default());
+                tags.insert(key, value);
+            }
+            rest = parts.next()?;
+        }
+
+        let mut prefix = None;
+        if let Some(prefixed) = rest.strip_prefix(':') {
+            let mut parts = prefixed.splitn(2, ' ');
+            prefix = Some(parts.next()?.to_owned());
+            rest = parts.next()?;
+        }
+
+        let (middle, trailing) = match rest.find(" :") {
+            Some(index) => (&rest[..index], Some(&rest[index + 2..])),
+            None => (rest, None),
+        };
+        let mut parts = middle.split(' ').filter(|part| !part.is_empty());
+        let command = parts.next()?.to_owned();
+        let mut params: Vec<String> = parts.map(|part| part.to_owned()).collect();
+        if let Some(trailing) = trailing {
+            params.push(trailing.to_owned());
+        }
+
+        Some(IrcMessage {
+            tags,
+            prefix,
+            command,
+            params,
+        })
+    }
+
+    /// The nick of whoever sent the message
+    pub fn get_nick(&self) -> Option<&str> {
+        self.prefix.as_ref()?.split('!').next()
+    }
+
+    pub fn get_tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .get(key)
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// The last parameter, which is the text of chat messages
+    pub fn get_text(&self) -> Option<&str> {
+        self.params.last().map(|text| text.as_str())
+    }
+}
+
+/// Tag values can't hold spaces, semicolons or line breaks, so IRC escapes them with backslashes
+pub fn escape_tag_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            ';' => escaped.push_str("\\:"),
+            ' ' => escaped.push_str("\\s"),
+            '\\' => escaped.push_str("\\\\"),
+            '\r' => escaped.push_str("\\r"),
+            '\n' => escaped.push_str("\\n"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn unescape_tag_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        // a backslash in front of anything else is dropped, as is one at the very end
+        match characters.next() {
+            Some(':') => unescaped.push(';'),
+            Some('s') => unescaped.push(' '),
+            Some('r') => unescaped.push('\r'),
+            Some('n') => unescaped.push('\n'),
+            Some(character) => unescaped.push(character),
+            None => {}
+        }
+    }
+    unescaped
+}
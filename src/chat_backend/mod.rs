@@ -0,0 +1,32 @@
+mod console_chat_backend;
+mod fake_irc_server;
+mod irc_message;
+mod twitch_chat_backend;
+
+use crate::chatter::Chatter;
+pub use console_chat_backend::ConsoleChatBackend;
+use eyre::Result;
+pub use fake_irc_server::FakeIrcServer;
+pub use twitch_chat_backend::{TwitchChatBackend, TwitchSettings};
+
+/// A message a viewer sent to chat
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub chatter: Chatter,
+    pub message: String,
+}
+
+impl ChatMessage {
+    pub fn new(chatter: Chatter, message: String) -> ChatMessage {
+        ChatMessage { chatter, message }
+    }
+}
+
+/// Where viewers play the game from, like Twitch chat
+pub trait ChatBackend {
+    /// The next message from chat if one has arrived, this should never block
+    fn receive(&mut self) -> Option<ChatMessage>;
+
+    /// Announce something to everyone in chat
+    fn send(&mut self, message: String) -> Result<()>;
+}
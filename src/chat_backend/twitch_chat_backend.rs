@@ -0,0 +1,209 @@
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use eyre::{eyre, Result};
+
+use super::{irc_message::IrcMessage, ChatBackend, ChatMessage};
+use crate::chatter::{Chatter, ChatterRole};
+
+const TWITCH_ADDRESS: &str = "irc.chat.twitch.tv:6667";
+
+/// Who to log in to Twitch chat as and which channel to play in
+#[derive(Debug, Clone)]
+pub struct TwitchSettings {
+    pub name: String,
+    pub token: String,
+    pub channel: String,
+    /// The chat server to connect to, this is only changed to point the game at a test server
+    pub address: String,
+}
+
+impl TwitchSettings {
+    /// Read the settings from the environment or the .env file
+    pub fn from_env() -> Result<TwitchSettings> {
+        dotenv::dotenv().ok();
+        Ok(TwitchSettings {
+            name: get_env("TWITCH_NAME")?,
+            token: get_env("TWITCH_TOKEN")?,
+            channel: get_env("TWITCH_CHANNEL")?,
+            address: env::var("TWITCH_ADDRESS").unwrap_or_else(|_| TWITCH_ADDRESS.to_owned()),
+        })
+    }
+}
+
+/// Plays the game through Twitch chat. Messages from chat are read on their own thread
+/// so the game never waits on the connection.
+pub struct TwitchChatBackend {
+    channel: String,
+    writer: Arc<Mutex<TcpStream>>,
+    receive_from_twitch: Receiver<ChatMessage>,
+}
+
+impl TwitchChatBackend {
+    pub fn connect(settings: &TwitchSettings) -> Result<TwitchChatBackend> {
+        let stream = TcpStream::connect(&settings.address).map_err(|error| {
+            eyre!(
+                "could not connect to Twitch chat at {}: {}",
+                settings.address,
+                error
+            )
+        })?;
+        let channel = format!("#{}", settings.channel.to_lowercase());
+        let token = if settings.token.starts_with("oauth:") {
+            settings.token.clone()
+        } else {
+            format!("oauth:{}", settings.token)
+        };
+
+        let mut writer = stream.try_clone()?;
+        // the tags are where Twitch puts display names, colors and subscriptions
+        write_line(&mut writer, "CAP REQ :twitch.tv/tags")?;
+        write_line(&mut writer, &format!("PASS {}", token))?;
+        write_line(
+            &mut writer,
+            &format!("NICK {}", settings.name.to_lowercase()),
+        )?;
+        write_line(&mut writer, &format!("JOIN {}", channel))?;
+        let writer = Arc::new(Mutex::new(writer));
+
+        let (send_to_game, receive_from_twitch) = mpsc::channel();
+        let pong_writer = Arc::clone(&writer);
+        thread::spawn(move || Self::listen(stream, pong_writer, send_to_game));
+
+        Ok(TwitchChatBackend {
+            channel,
+            writer,
+            receive_from_twitch,
+        })
+    }
+
+    fn listen(stream: TcpStream, writer: Arc<Mutex<TcpStream>>, send_to_game: Sender<ChatMessage>) {
+        for line in BufReader::new(stream).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => {
+                    eprintln!("Error reading from Twitch chat: {}", error);
+                    return;
+                }
+            };
+            let irc_message = match IrcMessage::parse(&line) {
+                Some(irc_message) => irc_message,
+                None => continue,
+            };
+            match irc_message.command.as_str() {
+                "PING" => {
+                    let pong = format!("PONG :{}", irc_message.get_text().unwrap_or_default());
+                    if let Ok(mut writer) = writer.lock() {
+                        if let Err(error) = write_line(&mut *writer, &pong) {
+                            eprintln!("Error answering Twitch chat's ping: {}", error);
+                        }
+                    }
+                }
+                "PRIVMSG" => {
+                    if let Some(chat_message) = Self::to_chat_message(&irc_message) {
+                        if send_to_game.send(chat_message).is_err() {
+                            return;
+                        }
+                    }
+                }
+                // this is how Twitch lets us know that logging in failed
+                "NOTICE" => eprintln!(
+                    "Twitch chat: {}",
+                    irc_message.get_text().unwrap_or_default()
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn to_chat_message(irc_message: &IrcMessage) -> Option<ChatMessage> {
+        let name = irc_message
+            .get_tag("display-name")
+            .or_else(|| irc_message.get_nick())?;
+        let color = irc_message
+            .get_tag("color")
+            .and_then(parse_color)
+            .unwrap_or((0, 0, 0));
+        let is_subscriber = irc_message.get_tag("subscriber") == Some("1");
+        let mut chatter =
+            Chatter::new(name.to_owned(), color, is_subscriber).with_role(get_role(irc_message));
+        if let Some(user_id) = irc_message.get_tag("user-id") {
+            chatter = chatter.with_user_id(user_id.to_owned());
+        }
+        Some(ChatMessage::new(
+            chatter,
+            irc_message.get_text()?.to_owned(),
+        ))
+    }
+}
+
+impl ChatBackend for TwitchChatBackend {
+    fn receive(&mut self) -> Option<ChatMessage> {
+        self.receive_from_twitch.try_recv().ok()
+    }
+
+    fn send(&mut self, message: String) -> Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| eyre!("the connection to Twitch chat was lost"))?;
+        write_line(
+            &mut *writer,
+            &format!("PRIVMSG {} :{}", self.channel, message),
+        )?;
+        Ok(())
+    }
+}
+
+impl Drop for TwitchChatBackend {
+    fn drop(&mut self) {
+        if let Ok(writer) = self.writer.lock() {
+            writer.shutdown(Shutdown::Both).ok();
+        }
+    }
+}
+
+fn get_env(key: &str) -> Result<String> {
+    env::var(key).map_err(|_| {
+        eyre!(
+            "{} is not set, see the README for setting up the .env file",
+            key
+        )
+    })
+}
+
+fn write_line(writer: &mut impl Write, line: &str) -> std::io::Result<()> {
+    write!(writer, "{}\r\n", line)?;
+    writer.flush()
+}
+
+/// Badges look like broadcaster/1,subscriber/12
+fn get_role(irc_message: &IrcMessage) -> ChatterRole {
+    let badges: Vec<&str> = irc_message
+        .get_tag("badges")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|badge| badge.split('/').next())
+        .collect();
+    if badges.contains(&"broadcaster") {
+        ChatterRole::Broadcaster
+    } else if badges.contains(&"moderator") || irc_message.get_tag("mod") == Some("1") {
+        ChatterRole::Moderator
+    } else {
+        ChatterRole::Viewer
+    }
+}
+
+/// Twitch colors look like #1E90FF
+fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+    let color = color.strip_prefix('#')?;
+    if color.len() != 6 {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(color.get(index..index + 2)?, 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
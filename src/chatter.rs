@@ -1,12 +1,27 @@
 use ggez::graphics::Color;
 
+/// names darker than this are hard to read on the dark parts of the screen, from 0 to 1
+const MIN_READABLE_BRIGHTNESS: f32 = 0.5;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Chatter {
+    /// Stays the same when the chatter changes their display name, scores are kept by this
+    pub user_id: String,
+    /// The display name, which is what the chatter wants to be called
     pub name: String,
     pub red: u8,
     pub green: u8,
     pub blue: u8,
     pub is_subscriber: bool,
+    pub role: ChatterRole,
+}
+
+/// Moderators and the broadcaster can control the game from chat
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatterRole {
+    Viewer,
+    Moderator,
+    Broadcaster,
 }
 
 impl Chatter {
@@ -18,15 +33,64 @@ impl Chatter {
         };
 
         Chatter {
+            user_id: name.to_lowercase(),
             name,
             red,
             green,
             blue,
             is_subscriber,
+            role: ChatterRole::Viewer,
         }
     }
 
+    /// Chatters are told apart by their lowercase name unless the chat knows their user id
+    pub fn with_user_id(mut self, user_id: String) -> Chatter {
+        self.user_id = user_id;
+        self
+    }
+
+    pub fn with_role(mut self, role: ChatterRole) -> Chatter {
+        self.role = role;
+        self
+    }
+
+    pub fn can_control_game(&self) -> bool {
+        matches!(self.role, ChatterRole::Moderator | ChatterRole::Broadcaster)
+    }
+
     pub fn get_color(&self) -> Color {
         Color::from_rgba(self.red, self.green, self.blue, 255)
     }
+
+    /// The chatter's color, lightened just enough to read when it is too dark
+    pub fn get_readable_color(&self) -> Color {
+        let color = self.get_color();
+        let brightness = 0.299 * color.r + 0.587 * color.g + 0.114 * color.b;
+        if brightness >= MIN_READABLE_BRIGHTNESS {
+            return color;
+        }
+        let lighten = |channel: f32| {
+            channel + (1.0 - channel) * (MIN_READABLE_BRIGHTNESS - brightness) / (1.0 - brightness)
+        };
+        Color::new(lighten(color.r), lighten(color.g), lighten(color.b), 1.0)
+    }
+}
+
+impl ChatterRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatterRole::Viewer => "viewer",
+            ChatterRole::Moderator => "moderator",
+            ChatterRole::Broadcaster => "broadcaster",
+        }
+    }
+
+    pub fn parse(role: &str) -> Option<ChatterRole> {
+        match role {
+            "viewer" => Some(ChatterRole::Viewer),
+            "moderator" => Some(ChatterRole::Moderator),
+            "broadcaster" => Some(ChatterRole::Broadcaster),
+            _ => None,
+        }
+    }
 }
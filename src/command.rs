@@ -1,102 +1,224 @@
-use ggez::{nalgebra::Point2, Context, GameResult};
-use rand::{distributions::Standard, prelude::Distribution, Rng};
+use ggez::nalgebra::Point2;
+use rand::Rng;
 
 use crate::{
-    draw_system::DrawSystem, draw_system::GameObjectDrawSystem, game_object::GameObject,
-    game_object_type::GameObjectType, life_system::FireLifeSystem, life_system::HeartLifeSystem,
-    life_system::LifeSystem, life_system::SnakeLifeSystem, life_system::SwordLifeSystem,
-    physics::FirePhysics, physics::HeartPhysics, physics::PhysicsSystem, physics::SnakePhysics,
-    physics::SwordPhysics, sprites::Sprite,
+    draw_system::DrawSystem,
+    draw_system::GameObjectDrawSystem,
+    game_object::GameObject,
+    game_object_type::GameObjectType,
+    life_system::FireLifeSystem,
+    life_system::HeartLifeSystem,
+    life_system::LifeSystem,
+    life_system::SnakeLifeSystem,
+    life_system::SwordLifeSystem,
+    object_registry::{ObjectBehavior, ObjectDefinition, ObjectRegistry},
+    physics::FirePhysics,
+    physics::HeartPhysics,
+    physics::PhysicsSystem,
+    physics::SnakePhysics,
+    physics::SwordPhysics,
+    sprites::Sprites,
 };
 
 use super::Chatter;
 
+const UNKNOWN_TARGET: &str =
+    "I couldn't tell where to drop, use a column or player, left, right, far or center";
+
 pub struct Command {
-    pub command_type: CommandType,
-    pub id: u8,
+    /// what to drop, from the object registry
+    pub object: ObjectDefinition,
+    pub target: DropTarget,
     pub chatter: Chatter,
 }
 
+/// Where a drop lands. Targets other than a column are worked out when the drop lands,
+/// so they follow the streamer however late the stream shows them to chat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DropTarget {
+    Column(u8),
+    /// right above the streamer
+    Player,
+    /// the column next to the streamer on their left, or on their right when they are
+    /// against the left wall
+    Left,
+    /// the column next to the streamer on their right, or on their left when they are
+    /// against the right wall
+    Right,
+    /// the column at the other end of the arena from the streamer
+    Far,
+    /// the middle of the arena
+    Center,
+}
+
+impl std::fmt::Display for DropTarget {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DropTarget::Column(column) => write!(formatter, "{}", column),
+            DropTarget::Player => write!(formatter, "player"),
+            DropTarget::Left => write!(formatter, "left"),
+            DropTarget::Right => write!(formatter, "right"),
+            DropTarget::Far => write!(formatter, "far"),
+            DropTarget::Center => write!(formatter, "center"),
+        }
+    }
+}
+
+impl DropTarget {
+    fn from_name(name: &str) -> Option<DropTarget> {
+        match name.to_lowercase().as_str() {
+            "player" => Some(DropTarget::Player),
+            "left" => Some(DropTarget::Left),
+            "right" => Some(DropTarget::Right),
+            "far" => Some(DropTarget::Far),
+            "center" | "centre" | "middle" => Some(DropTarget::Center),
+            _ => None,
+        }
+    }
+
+    /// The column to drop into when the streamer is standing in `player_column`
+    pub fn get_column(&self, player_column: u8, drop_zone_count: u8) -> u8 {
+        let last_column = drop_zone_count - 1;
+        match *self {
+            DropTarget::Column(column) => column,
+            DropTarget::Player => player_column,
+            DropTarget::Left if player_column == 0 => 1.min(last_column),
+            DropTarget::Left => player_column - 1,
+            DropTarget::Right if player_column == last_column => last_column.saturating_sub(1),
+            DropTarget::Right => player_column + 1,
+            DropTarget::Far if player_column > last_column / 2 => 0,
+            DropTarget::Far => last_column,
+            DropTarget::Center => drop_zone_count / 2,
+        }
+    }
+}
+
 impl Command {
-    pub fn new(message: &str, chatter: Chatter) -> Result<Option<Command>, &'static str> {
-        if !message.starts_with('#') {
-            return Ok(None);
+    /// Every drop asked for in a message like `#fire 3 #sword 2-4`. Each drop names an object
+    /// and can be followed by where to drop it, as a column, a range like `2-4`, a target like
+    /// `player` or a list like `1,far`, and without one it drops into a random column. Hashtags that aren't
+    /// objects are left alone unless they look like a typo of one. Drops that can't be made
+    /// sense of are left out and the rest still drop, along with what to tell the chatter
+    /// about the ones that were left out.
+    pub fn parse(
+        message: &str,
+        chatter: &Chatter,
+        objects: &ObjectRegistry,
+        drop_zone_count: u8,
+        rng: &mut impl Rng,
+    ) -> (Vec<Command>, Vec<String>) {
+        let mut commands = vec![];
+        let mut problems = vec![];
+        let mut words = message.split_whitespace().peekable();
+        match words.peek() {
+            Some(word) if word.starts_with('#') => {}
+            _ => return (commands, problems),
         }
 
-        let mut parts = message.split(' ');
-        if let Some(command) = parts.next() {
-            let id = Self::get_id_from_message(parts.next())?;
-            match command {
-                "#fire" => Ok(Some(Command {
-                    command_type: CommandType::Fire,
-                    id,
-                    chatter,
-                })),
-                "#sword" => Ok(Some(Command {
-                    command_type: CommandType::Sword,
-                    id,
-                    chatter,
-                })),
-                "#snake" | "#snek" => Ok(Some(Command {
-                    command_type: CommandType::Snake,
-                    id,
-                    chatter,
-                })),
-                "#heart" => Ok(Some(Command {
-                    command_type: CommandType::Heart,
-                    id,
-                    chatter,
-                })),
-                "#random" | "#rand" | "#rng" => Ok(Some(Command {
-                    command_type: CommandType::random(),
-                    id,
-                    chatter,
-                })),
-                _ => Ok(None),
+        while let Some(word) = words.next() {
+            if !word.starts_with('#') {
+                continue;
+            }
+            let object = if ObjectRegistry::is_random_command(word) {
+                None
+            } else {
+                match objects.find_by_command(word) {
+                    Some(object) => Some(object),
+                    None => {
+                        if let Some(suggestion) = objects.suggest_command(word) {
+                            problems.push(format!("Did you mean {}?", suggestion));
+                        }
+                        continue;
+                    }
+                }
+            };
+
+            let targets = match words.peek() {
+                Some(targets) if !targets.starts_with('#') => {
+                    let targets = Self::get_targets(targets, drop_zone_count);
+                    words.next();
+                    match targets {
+                        Ok(targets) => targets,
+                        Err(problem) => {
+                            problems.push(problem);
+                            continue;
+                        }
+                    }
+                }
+                _ => vec![DropTarget::Column(rng.gen_range(0, drop_zone_count))],
+            };
+            for target in targets {
+                let object = object.unwrap_or_else(|| objects.choose_random(rng));
+                commands.push(Command {
+                    object: object.clone(),
+                    target,
+                    chatter: chatter.clone(),
+                });
             }
-        } else {
-            Ok(None)
         }
+        (commands, problems)
     }
 
-    fn get_id_from_message(message_part: Option<&str>) -> Result<u8, &'static str> {
-        if let Some(id) = message_part {
-            match id.parse::<u8>() {
-                Ok(number) => {
-                    if number < crate::DROP_ZONE_COUNT {
-                        Ok(number)
-                    } else {
-                        Err("The given column is outside of the arena")
-                    }
+    fn get_targets(targets: &str, drop_zone_count: u8) -> Result<Vec<DropTarget>, String> {
+        let parse_column = |column: &str| match column.parse::<u8>() {
+            Ok(number) if number < drop_zone_count => Ok(number),
+            Ok(_) => Err("The given column is outside of the arena".to_owned()),
+            Err(_error) => Err(UNKNOWN_TARGET.to_owned()),
+        };
+
+        let mut all_targets = vec![];
+        let mut add_target = |target| {
+            if !all_targets.contains(&target) {
+                all_targets.push(target);
+            }
+        };
+        for part in targets.split(',').filter(|part| !part.is_empty()) {
+            if let Some(target) = DropTarget::from_name(part) {
+                add_target(target);
+                continue;
+            }
+            let (first, last) = match part.split_once('-') {
+                Some((first, last)) => (parse_column(first)?, parse_column(last)?),
+                None => {
+                    let id = parse_column(part)?;
+                    (id, id)
                 }
-                Err(_error) => Err("I couldn't tell what column to drop into"),
+            };
+            for column in first.min(last)..=first.max(last) {
+                add_target(DropTarget::Column(column));
             }
-        } else {
-            let mut rng = rand::thread_rng();
-            Ok(rng.gen_range(0, crate::DROP_ZONE_COUNT))
         }
+        if all_targets.is_empty() {
+            return Err(UNKNOWN_TARGET.to_owned());
+        }
+        Ok(all_targets)
     }
 
+    /// Create the game object for this command. Without sprites the object is created
+    /// without a draw system, which is what running the game headless relies on. Its size
+    /// comes from the object registry either way so collisions are the same headless.
     pub fn handle(
         &self,
         drop_zone_location: Point2<f32>,
-        context: &mut Context,
-    ) -> GameResult<GameObject> {
-        let scale = self.get_scale();
-        let sprite = self.get_sprite(context)?;
-        let label_color = if self.chatter.is_subscriber {
-            self.chatter.get_color()
-        } else {
-            ggez::graphics::WHITE
-        };
-        let label = Some((self.chatter.name.clone(), label_color));
-        let draw_system = GameObjectDrawSystem::new(Some(sprite), label, scale);
-        let size = draw_system.get_size().unwrap_or((50.0, 50.0));
-        let physics_system = self.get_physics();
-        let game_object = GameObject::new(
+        sprites: Option<&Sprites>,
+        rng: &mut impl Rng,
+    ) -> GameObject {
+        let size = (self.object.width, self.object.height);
+        let draw_system = sprites.map(|sprites| {
+            let label_color = if self.chatter.is_subscriber {
+                self.chatter.get_color()
+            } else {
+                ggez::graphics::WHITE
+            };
+            let label = Some((self.chatter.name.clone(), label_color));
+            let sprite = sprites.get_object(&self.object.name).cloned();
+            GameObjectDrawSystem::new(sprite, label, size)
+        });
+        let physics_system = self.get_physics(rng);
+        GameObject::new(
             drop_zone_location.x - size.0 / 2.0,
             drop_zone_location.y - size.1 / 2.0,
-            Some(Box::new(draw_system)),
+            draw_system.map(|draw_system| Box::new(draw_system) as Box<dyn DrawSystem>),
             size.0,
             size.1,
             physics_system,
@@ -104,80 +226,37 @@ impl Command {
             Some(self.chatter.clone()),
             self.get_game_object_type(),
             self.get_life_system(),
-        );
-        Ok(game_object)
-    }
-
-    fn get_scale(&self) -> f32 {
-        match self.command_type {
-            CommandType::Fire => 2.0,
-            CommandType::Sword => 3.0,
-            CommandType::Snake => 3.0,
-            CommandType::Heart => 1.5,
-        }
-    }
-
-    fn get_sprite(&self, context: &mut Context) -> GameResult<Sprite> {
-        match self.command_type {
-            CommandType::Fire => Sprite::new(context, "/LargeFlame.png", 4, 1),
-            CommandType::Sword => Sprite::new(context, "/item1BIT_sword.png", 1, 1),
-            CommandType::Snake => Sprite::new(context, "/snake.png", 4, 1),
-            CommandType::Heart => Sprite::new(context, "/heart.png", 1, 1),
-        }
+        )
+        .with_damage(self.object.damage)
     }
 
-    fn get_physics(&self) -> Option<Box<dyn PhysicsSystem>> {
-        match self.command_type {
-            CommandType::Fire => Some(Box::new(FirePhysics::new())),
-            CommandType::Sword => Some(Box::new(SwordPhysics::new())),
-            CommandType::Snake => Some(Box::new(SnakePhysics::new())),
-            CommandType::Heart => Some(Box::new(HeartPhysics::new())),
+    fn get_physics(&self, rng: &mut impl Rng) -> Option<Box<dyn PhysicsSystem>> {
+        match self.object.behavior {
+            ObjectBehavior::Fire => Some(Box::new(FirePhysics::new())),
+            ObjectBehavior::Sword => Some(Box::new(SwordPhysics::new(rng.gen::<f32>() * 900.0))),
+            ObjectBehavior::Snake => Some(Box::new(SnakePhysics::new())),
+            ObjectBehavior::Heart => Some(Box::new(HeartPhysics::new())),
         }
     }
 
     fn get_life_system(&self) -> Option<Box<dyn LifeSystem>> {
-        match self.command_type {
-            CommandType::Fire => Some(Box::new(FireLifeSystem::new())),
-            CommandType::Sword => Some(Box::new(SwordLifeSystem::new())),
-            CommandType::Snake => Some(Box::new(SnakeLifeSystem::new())),
-            CommandType::Heart => Some(Box::new(HeartLifeSystem::new())),
+        match self.object.behavior {
+            ObjectBehavior::Fire => {
+                let lifetime = self.object.lifetime().unwrap_or_default();
+                Some(Box::new(FireLifeSystem::new(lifetime)))
+            }
+            ObjectBehavior::Sword => Some(Box::new(SwordLifeSystem::new(
+                self.object.bounces.unwrap_or(1),
+            ))),
+            ObjectBehavior::Snake => Some(Box::new(SnakeLifeSystem::new())),
+            ObjectBehavior::Heart => Some(Box::new(HeartLifeSystem::new())),
         }
     }
 
     fn get_game_object_type(&self) -> GameObjectType {
-        match self.command_type {
-            CommandType::Heart => GameObjectType::Heart,
+        match self.object.behavior {
+            ObjectBehavior::Heart => GameObjectType::Heart,
             _ => GameObjectType::Enemy,
         }
     }
 }
-
-#[derive(PartialEq, Debug)]
-pub enum CommandType {
-    Fire,
-    Sword,
-    Snake,
-    Heart,
-}
-
-impl CommandType {
-    pub fn random() -> Self {
-        rand::random()
-    }
-}
-
-impl Distribution<CommandType> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> CommandType {
-        let random: f32 = rng.gen();
-
-        if random < 0.1 {
-            CommandType::Heart
-        } else if random >= 0.1 && random < 0.4 {
-            CommandType::Fire
-        } else if random >= 0.4 && random < 0.7 {
-            CommandType::Sword
-        } else {
-            CommandType::Snake
-        }
-    }
-}
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+
+use crate::{
+    command::Command,
+    config::{Config, OverflowPolicy},
+};
+
+/// Drops wait here between arriving from chat and landing in the arena, so a raid sending
+/// a wall of commands at once has them spread out over the next ticks instead of all at once.
+/// Drops that waited too long are thrown out rather than landing seconds after they were sent.
+#[derive(Default)]
+pub struct CommandQueue {
+    /// the drops with the tick they were queued at, oldest first
+    queue: VecDeque<(u32, Command)>,
+    metrics: CommandQueueMetrics,
+}
+
+/// How busy the drop queue has been, for keeping an eye on it during busy streams
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandQueueMetrics {
+    /// how many drops are waiting right now
+    pub depth: usize,
+    /// the most drops that have been waiting at the same time
+    pub max_depth: usize,
+    /// the longest any drop waited before landing
+    pub max_wait_ticks: u32,
+    pub spawned: u32,
+    /// same object in the same column as a drop that was already waiting
+    pub merged: u32,
+    /// thrown out to make room when the queue was full
+    pub dropped: u32,
+    /// waited too long or were still waiting when the round ended
+    pub expired: u32,
+}
+
+impl CommandQueue {
+    pub fn push(&mut self, command: Command, tick: u32, config: &Config) {
+        let queue_config = &config.queue;
+        if self.queue.len() >= queue_config.max_length {
+            if queue_config.overflow == OverflowPolicy::MergeDuplicates
+                && self.queue.iter().any(|(_, queued)| {
+                    queued.object.name == command.object.name && queued.target == command.target
+                })
+            {
+                self.metrics.merged += 1;
+                return;
+            }
+            self.queue.pop_front();
+            self.metrics.dropped += 1;
+        }
+
+        self.queue.push_back((tick, command));
+        self.update_depth();
+    }
+
+    /// The drops that should land this tick, oldest first
+    pub fn take_ready(&mut self, tick: u32, config: &Config) -> Vec<Command> {
+        let max_wait_ticks = config.ticks_in(config.queue.max_wait());
+        let queued = self.queue.len();
+        self.queue
+            .retain(|(queued_at_tick, _)| tick - queued_at_tick <= max_wait_ticks);
+        self.metrics.expired += (queued - self.queue.len()) as u32;
+
+        let count = self
+            .queue
+            .len()
+            .min(config.queue.max_spawns_per_tick as usize);
+        let metrics = &mut self.metrics;
+        let ready: Vec<Command> = self
+            .queue
+            .drain(..count)
+            .map(|(queued_at_tick, command)| {
+                metrics.max_wait_ticks = metrics.max_wait_ticks.max(tick - queued_at_tick);
+                command
+            })
+            .collect();
+        self.metrics.spawned += ready.len() as u32;
+        self.update_depth();
+        ready
+    }
+
+    /// Throw out everything still waiting, for when the round is over
+    pub fn clear(&mut self) {
+        self.metrics.expired += self.queue.len() as u32;
+        self.queue.clear();
+        self.update_depth();
+    }
+
+    /// The drops still waiting, in the order they will land
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.queue.iter().map(|(_, command)| command)
+    }
+
+    pub fn get_metrics(&self) -> &CommandQueueMetrics {
+        &self.metrics
+    }
+
+    fn update_depth(&mut self) {
+        self.metrics.depth = self.queue.len();
+        self.metrics.max_depth = self.metrics.max_depth.max(self.queue.len());
+    }
+}
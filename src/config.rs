@@ -0,0 +1,354 @@
+use std::io::Read;
+use std::time::Duration;
+
+use eyre::{eyre, Result};
+use serde::Deserialize;
+
+use crate::{object_registry::ObjectRegistry, utilities};
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Everything that tunes how the game plays. Any key left out of the config file
+/// keeps its default value.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub seed: Option<u64>,
+    pub world: WorldConfig,
+    pub round: RoundConfig,
+    pub player: PlayerConfig,
+    /// what chat can drop, from `objects.toml` rather than the config file
+    #[serde(skip)]
+    pub objects: ObjectRegistry,
+    pub chat: ChatConfig,
+    pub drops: DropsConfig,
+    pub queue: QueueConfig,
+    pub session: SessionConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorldConfig {
+    pub framerate_target: u32,
+    /// pixels per second, every second
+    pub gravity_force: f32,
+    pub drop_zone_count: u8,
+    /// how fast game time runs, 1.0 is real time, 2.0 is turbo mode
+    pub time_scale: f32,
+    /// the most updates a slow frame can catch up on, past that the game slows down instead
+    pub max_updates_per_frame: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoundConfig {
+    pub game_time_seconds: u64,
+    pub splash_duration_seconds: u64,
+    pub lives: u8,
+    /// the round waits after the splash screen until this many chatters have joined, 0 doesn't wait
+    pub min_players: u32,
+    /// the last seconds of the round play out in slow motion, 0 turns the finale off
+    pub finale_seconds: f32,
+    /// how fast game time runs during the finale, 0.5 is half speed
+    pub finale_time_scale: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    /// pixels per second the player speeds up by every second a key is held
+    pub move_force: f32,
+    /// pixels per second
+    pub jump_force: f32,
+    /// how quickly the player's sideways speed dies down, higher stops sooner
+    pub friction: f32,
+    /// how many seconds the player can't be hit for after getting hit at the start of the round
+    pub iframes_start_seconds: f32,
+    pub iframes_min_seconds: f32,
+    pub iframes_reduce_by_seconds: f32,
+    pub iframes_reduce_every_seconds: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChatConfig {
+    /// how long a chatter waits before the same leaderboard command is answered for them again
+    pub leaderboard_cooldown_seconds: u64,
+    /// the least amount of time between any two leaderboard answers
+    pub leaderboard_interval_seconds: u64,
+}
+
+/// Limits on dropping objects so one chatter can't fill the arena, 0 turns a limit off
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DropsConfig {
+    /// how long each chatter has to wait between drops
+    pub cooldown_seconds: f32,
+    /// how many objects each chatter can drop in a round
+    pub per_round: u32,
+    /// how many objects all of chat together can drop every second
+    pub max_per_second: u32,
+    /// how long the drop zone warns the streamer before the object lands, 0 drops it straight away
+    pub warning_seconds: f32,
+}
+
+/// A session is every round played before the game closes
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionConfig {
+    /// how many rounds to play before the game closes, 0 keeps playing until the window is closed
+    pub rounds: u32,
+    /// how long the credits roll between rounds before the next one starts
+    pub break_seconds: u64,
+}
+
+/// How drops from chat are spread out over the ticks after they arrive
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QueueConfig {
+    /// how many objects can land in the same tick
+    pub max_spawns_per_tick: u32,
+    /// how many drops can wait at the same time before the overflow policy kicks in
+    pub max_length: usize,
+    /// drops that waited longer than this are thrown out
+    pub max_wait_seconds: f32,
+    pub overflow: OverflowPolicy,
+}
+
+/// What happens to a drop that arrives when the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// the drop that has waited the longest is thrown out to make room
+    DropOldest,
+    /// a drop of the same object in the same column as one that is already waiting is merged
+    /// into it, anything else makes room like `drop_oldest`
+    MergeDuplicates,
+}
+
+impl Config {
+    /// Load the config from the given path, or from `config.toml` in the resources folder
+    /// when no path is given. The defaults are used if there is no config file in the resources folder.
+    pub fn load(path: Option<&str>) -> Result<Config> {
+        let mut config_file = match path {
+            Some(path) => std::fs::File::open(path)
+                .map_err(|error| eyre!("could not open config file {}: {}", path, error))?,
+            None => match utilities::get_resource(CONFIG_FILE_NAME) {
+                Some(config_file) => config_file,
+                None => return Ok(Config::default()),
+            },
+        };
+        let mut config = String::new();
+        config_file.read_to_string(&mut config)?;
+        Self::from_toml(&config)
+    }
+
+    pub fn from_toml(config: &str) -> Result<Config> {
+        let config: Config =
+            toml::from_str(config).map_err(|error| eyre!("invalid config: {}", error))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn ticks_in(&self, duration: Duration) -> u32 {
+        (duration.as_secs_f64() * self.world.framerate_target as f64).round() as u32
+    }
+
+    fn validate(&self) -> Result<()> {
+        let player = &self.player;
+        ensure(self.world.framerate_target > 0, "world.framerate_target")?;
+        ensure(self.world.gravity_force > 0.0, "world.gravity_force")?;
+        ensure(self.world.drop_zone_count > 0, "world.drop_zone_count")?;
+        ensure(self.world.time_scale > 0.0, "world.time_scale")?;
+        ensure(
+            self.world.max_updates_per_frame > 0,
+            "world.max_updates_per_frame",
+        )?;
+        ensure(self.round.game_time_seconds > 0, "round.game_time_seconds")?;
+        ensure(self.round.lives > 0, "round.lives")?;
+        ensure(
+            self.round.finale_time_scale > 0.0,
+            "round.finale_time_scale",
+        )?;
+        ensure(player.move_force > 0.0, "player.move_force")?;
+        ensure(player.jump_force > 0.0, "player.jump_force")?;
+        ensure(
+            player.iframes_reduce_every_seconds > 0.0,
+            "player.iframes_reduce_every_seconds",
+        )?;
+
+        ensure(
+            self.queue.max_spawns_per_tick > 0,
+            "queue.max_spawns_per_tick",
+        )?;
+        ensure(self.queue.max_length > 0, "queue.max_length")?;
+        ensure(self.queue.max_wait_seconds > 0.0, "queue.max_wait_seconds")?;
+
+        if !self.round.finale_seconds.is_finite() || self.round.finale_seconds < 0.0 {
+            return Err(eyre!(
+                "invalid config: `round.finale_seconds` can't be less than 0"
+            ));
+        }
+        if self.drops.cooldown_seconds < 0.0 {
+            return Err(eyre!(
+                "invalid config: `drops.cooldown_seconds` can't be less than 0"
+            ));
+        }
+        if self.drops.warning_seconds < 0.0 {
+            return Err(eyre!(
+                "invalid config: `drops.warning_seconds` can't be less than 0"
+            ));
+        }
+
+        if player.friction < 0.0 {
+            return Err(eyre!(
+                "invalid config: `player.friction` can't be less than 0"
+            ));
+        }
+        if player.iframes_min_seconds < 0.0 || player.iframes_reduce_by_seconds < 0.0 {
+            return Err(eyre!(
+                "invalid config: `player.iframes_min_seconds` and `player.iframes_reduce_by_seconds` can't be less than 0"
+            ));
+        }
+        if player.iframes_min_seconds > player.iframes_start_seconds {
+            return Err(eyre!(
+                "invalid config: `player.iframes_min_seconds` can't be more than `player.iframes_start_seconds`"
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ChatConfig {
+    pub fn leaderboard_cooldown(&self) -> Duration {
+        Duration::from_secs(self.leaderboard_cooldown_seconds)
+    }
+
+    pub fn leaderboard_interval(&self) -> Duration {
+        Duration::from_secs(self.leaderboard_interval_seconds)
+    }
+}
+
+impl DropsConfig {
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs_f32(self.cooldown_seconds)
+    }
+
+    pub fn warning(&self) -> Duration {
+        Duration::from_secs_f32(self.warning_seconds)
+    }
+}
+
+impl SessionConfig {
+    pub fn break_duration(&self) -> Duration {
+        Duration::from_secs(self.break_seconds)
+    }
+}
+
+impl QueueConfig {
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_secs_f32(self.max_wait_seconds)
+    }
+}
+
+impl RoundConfig {
+    pub fn game_time(&self) -> Duration {
+        Duration::from_secs(self.game_time_seconds)
+    }
+
+    pub fn splash_duration(&self) -> Duration {
+        Duration::from_secs(self.splash_duration_seconds)
+    }
+
+    pub fn finale(&self) -> Duration {
+        Duration::from_secs_f32(self.finale_seconds)
+    }
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            framerate_target: 60,
+            gravity_force: 1080.0,
+            drop_zone_count: 10,
+            time_scale: 1.0,
+            max_updates_per_frame: 5,
+        }
+    }
+}
+
+impl Default for RoundConfig {
+    fn default() -> Self {
+        Self {
+            game_time_seconds: 120,
+            splash_duration_seconds: 15,
+            lives: 3,
+            min_players: 0,
+            finale_seconds: 0.0,
+            finale_time_scale: 0.5,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self {
+            move_force: 7200.0,
+            jump_force: 750.0,
+            friction: 10.0,
+            iframes_start_seconds: 2.0,
+            iframes_min_seconds: 0.2,
+            iframes_reduce_by_seconds: 0.2,
+            iframes_reduce_every_seconds: 12.0,
+        }
+    }
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            leaderboard_cooldown_seconds: 30,
+            leaderboard_interval_seconds: 2,
+        }
+    }
+}
+
+impl Default for DropsConfig {
+    fn default() -> Self {
+        Self {
+            cooldown_seconds: 2.0,
+            per_round: 30,
+            max_per_second: 4,
+            warning_seconds: 0.0,
+        }
+    }
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            rounds: 0,
+            break_seconds: 20,
+        }
+    }
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_spawns_per_tick: 2,
+            max_length: 20,
+            max_wait_seconds: 2.0,
+            overflow: OverflowPolicy::MergeDuplicates,
+        }
+    }
+}
+
+/// Values that need to be more than zero
+fn ensure(is_valid: bool, key: &str) -> Result<()> {
+    if is_valid {
+        Ok(())
+    } else {
+        Err(eyre!("invalid config: `{}` must be more than 0", key))
+    }
+}
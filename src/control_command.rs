@@ -0,0 +1,36 @@
+/// Commands for moderators and the streamer to run the game from chat, like `!gts pause`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    Pause,
+    Resume,
+    Restart,
+    End,
+    /// ignore everything the chatter with this lowercase name says for the rest of the
+    /// session, even if they change their name
+    Ban(String),
+}
+
+impl ControlCommand {
+    pub fn new(message: &str) -> Result<Option<ControlCommand>, &'static str> {
+        let mut parts = message.split_whitespace();
+        match parts.next() {
+            Some(prefix) if prefix.eq_ignore_ascii_case("!gts") => {}
+            _ => return Ok(None),
+        }
+
+        let command = parts.next().unwrap_or_default().to_lowercase();
+        match command.as_str() {
+            "pause" => Ok(Some(ControlCommand::Pause)),
+            "resume" => Ok(Some(ControlCommand::Resume)),
+            "restart" => Ok(Some(ControlCommand::Restart)),
+            "end" => Ok(Some(ControlCommand::End)),
+            "ban" => match parts.next() {
+                Some(name) => Ok(Some(ControlCommand::Ban(
+                    name.trim_start_matches('@').to_lowercase(),
+                ))),
+                None => Err("Who should be banned? Use !gts ban <user>"),
+            },
+            _ => Err("Use !gts pause, resume, restart, end or ban <user>"),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use super::utilities;
-use crate::running_state::RunningState;
+use crate::{running_state::RunningState, score::Score};
 use ggez::{
     graphics::DrawParam,
     graphics::Font,
@@ -10,8 +10,8 @@ use ggez::{
     nalgebra::Point2,
     Context, GameResult,
 };
-use rand::prelude::*;
 use rand::seq::IteratorRandom;
+use rand::Rng;
 
 const VELOCITY_Y: f32 = -2.0;
 const DEFAULT_STREAMER_WIN_MESSAGE: &str = "Streamer won!";
@@ -33,15 +33,16 @@ impl Credits {
         running_state: RunningState,
         context: &mut Context,
         screen_size: (f32, f32),
-        high_scores: &HashMap<String, u128>,
-        scores: &HashMap<String, u128>,
+        high_scores: &HashMap<String, Score>,
+        scores: &HashMap<String, Score>,
+        rng: &mut impl Rng,
     ) -> GameResult<Self> {
         let file_name = if matches!(running_state, RunningState::PlayerWon) {
             "streamer_wins_messages.txt"
         } else {
             "chat_wins_messages.txt"
         };
-        let snarky_message = Self::get_random_message(file_name);
+        let snarky_message = Self::get_random_message(file_name, rng);
         let mut credit_y = screen_size.1;
 
         let mut all_credits = vec![];
@@ -64,9 +65,12 @@ impl Credits {
             &mut credit_y,
         );
 
-        scores.iter().for_each(|(username, score)| {
-            let high_score = high_scores.get(username).unwrap_or(score);
-            let title = format!("{} - {} -> {}", username, score, high_score);
+        scores.iter().for_each(|(user_id, score)| {
+            let high_score = high_scores
+                .get(user_id)
+                .map(|high_score| high_score.points)
+                .unwrap_or(score.points);
+            let title = format!("{} - {} -> {}", score.name, score.points, high_score);
             Self::create_credit(
                 context,
                 screen_size,
@@ -113,11 +117,10 @@ impl Credits {
         Ok(Credits { all_credits })
     }
 
-    fn get_random_message(file_name: &str) -> String {
-        let mut rng = thread_rng();
+    fn get_random_message(file_name: &str, rng: &mut impl Rng) -> String {
         if let Some(messages) = utilities::load_messages(file_name) {
             messages
-                .choose(&mut rng)
+                .choose(rng)
                 .unwrap_or_else(|| DEFAULT_STREAMER_WIN_MESSAGE.to_owned())
         } else {
             DEFAULT_STREAMER_WIN_MESSAGE.to_owned()
@@ -1,4 +1,5 @@
 use super::{DrawSystem, Sprite};
+use crate::game_clock::GameClock;
 use ggez::graphics::{Color, DrawParam, Font, Scale, Text, TextFragment};
 use ggez::nalgebra::Point2;
 use ggez::{graphics, Context, GameResult};
@@ -7,14 +8,15 @@ use ggez::{graphics, Context, GameResult};
 pub struct GameObjectDrawSystem {
     sprite: Option<Sprite>,
     label: Option<Text>,
-    scale_by: f32,
+    /// the sprite is stretched to fill the object
+    size: (f32, f32),
 }
 
 impl GameObjectDrawSystem {
     pub fn new(
         sprite: Option<Sprite>,
         label: Option<(String, Color)>,
-        scale_by: f32,
+        size: (f32, f32),
     ) -> GameObjectDrawSystem {
         let label = label.map(|(text, color)| {
             Text::new(
@@ -27,15 +29,15 @@ impl GameObjectDrawSystem {
         GameObjectDrawSystem {
             sprite,
             label,
-            scale_by,
+            size,
         }
     }
 }
 
 impl DrawSystem for GameObjectDrawSystem {
-    fn update(&mut self, time_since_start: std::time::Duration, _velocity_x: f32) {
+    fn update(&mut self, clock: &GameClock, _velocity_x: f32) {
         if let Some(sprite) = &mut self.sprite {
-            sprite.update(time_since_start);
+            sprite.update(clock.get_elapsed());
         }
     }
 
@@ -44,13 +46,13 @@ impl DrawSystem for GameObjectDrawSystem {
             sprite.draw(
                 context,
                 location,
-                [self.scale_by, self.scale_by],
+                [self.size.0 / sprite.width, self.size.1 / sprite.height],
                 rotation,
                 None,
             )?;
         }
 
-        let size = self.get_size().unwrap_or((50.0, 50.0));
+        let size = self.size;
         if let Some(label) = &self.label {
             let label_width = label.width(context) as f32;
             let label_height = label.height(context) as f32;
@@ -77,10 +79,6 @@ impl DrawSystem for GameObjectDrawSystem {
     }
 
     fn get_size(&self) -> Option<(f32, f32)> {
-        if let Some(sprite) = &self.sprite {
-            Some((sprite.width * self.scale_by, sprite.height * self.scale_by))
-        } else {
-            None
-        }
+        Some(self.size)
     }
 }
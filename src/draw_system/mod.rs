@@ -3,6 +3,7 @@ mod player_draw_system;
 mod timer_draw_system;
 
 use super::Sprite;
+use crate::game_clock::GameClock;
 pub use game_object_draw_system::GameObjectDrawSystem;
 use ggez::nalgebra::Point2;
 use ggez::{Context, GameResult};
@@ -13,7 +14,7 @@ pub trait DrawSystem
 where
     Self: std::fmt::Debug,
 {
-    fn update(&mut self, time_since_start: std::time::Duration, velocity_x: f32);
+    fn update(&mut self, clock: &GameClock, velocity_x: f32);
     fn draw(&self, context: &mut Context, location: Point2<f32>, rotation: &f32) -> GameResult<()>;
     fn get_size(&self) -> Option<(f32, f32)>;
 }
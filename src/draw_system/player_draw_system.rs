@@ -1,6 +1,6 @@
 use ggez::{nalgebra::Point2, Context, GameResult};
 
-use crate::sprites::Sprite;
+use crate::{game_clock::GameClock, sprites::Sprite};
 
 use super::DrawSystem;
 
@@ -20,7 +20,9 @@ pub struct PlayerDrawSystem {
 }
 
 impl PlayerDrawSystem {
-    pub fn new(left_sprite: Sprite, forward_sprite: Sprite, scale_by: f32) -> Self {
+    /// The sprites are scaled up to fill the given size
+    pub fn new(left_sprite: Sprite, forward_sprite: Sprite, size: (f32, f32)) -> Self {
+        let scale_by = size.0 / forward_sprite.width;
         Self {
             left_sprite,
             forward_sprite,
@@ -31,7 +33,7 @@ impl PlayerDrawSystem {
 }
 
 impl DrawSystem for PlayerDrawSystem {
-    fn update(&mut self, _time_since_start: std::time::Duration, velocity_x: f32) {
+    fn update(&mut self, _clock: &GameClock, velocity_x: f32) {
         if velocity_x < -0.1 {
             self.facing = Facing::Left;
         } else if velocity_x > 0.1 {
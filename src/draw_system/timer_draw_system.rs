@@ -1,4 +1,5 @@
 use super::DrawSystem;
+use crate::game_clock::GameClock;
 use ggez::graphics::{Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect};
 use ggez::nalgebra::Point2;
 use ggez::{graphics, Context, GameResult};
@@ -35,7 +36,7 @@ impl TimerDrawSystem {
 }
 
 impl DrawSystem for TimerDrawSystem {
-    fn update(&mut self, _time_since_start: std::time::Duration, _velocity_x: f32) {}
+    fn update(&mut self, _clock: &GameClock, _velocity_x: f32) {}
 
     fn draw(
         &self,
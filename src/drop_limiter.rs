@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{chatter::Chatter, config::Config};
+
+/// Keeps one chatter from filling the arena by spamming drop commands. Each chatter has to
+/// wait between drops and only gets so many drops a round, and all of chat together can
+/// only drop so many objects every second.
+#[derive(Debug, Default)]
+pub struct DropLimiter {
+    /// keyed by the chatters' user ids
+    last_drop_at_tick: HashMap<String, u32>,
+    drops_this_round: HashMap<String, u32>,
+    /// the ticks of the drops in the last second
+    recent_drops: VecDeque<u32>,
+    /// chatters that were told why they can't drop, they aren't told again until they have dropped
+    told: HashSet<String>,
+}
+
+impl DropLimiter {
+    /// Count as many of the drops from one message as the chatter is allowed to drop right now,
+    /// the whole message waits out the cooldown once. The error is what to tell the chatter,
+    /// which is `None` when they already know they have to wait.
+    pub fn allow_drops(
+        &mut self,
+        chatter: &Chatter,
+        drops: usize,
+        tick: u32,
+        config: &Config,
+    ) -> Result<usize, Option<String>> {
+        self.allow(chatter, drops, tick, config, true)
+    }
+
+    /// Like `allow_drops` for the drops chatters sent before the round started. Those all
+    /// land as the round starts, so they don't count towards how fast all of chat is dropping.
+    pub fn allow_early_drops(
+        &mut self,
+        chatter: &Chatter,
+        drops: usize,
+        tick: u32,
+        config: &Config,
+    ) -> Result<usize, Option<String>> {
+        self.allow(chatter, drops, tick, config, false)
+    }
+
+    fn allow(
+        &mut self,
+        chatter: &Chatter,
+        drops: usize,
+        tick: u32,
+        config: &Config,
+        counts_towards_chat: bool,
+    ) -> Result<usize, Option<String>> {
+        let allowed = match self.check(chatter, tick, config, counts_towards_chat) {
+            Ok(room) => drops.min(room),
+            Err(reason) => {
+                return if self.told.insert(chatter.user_id.clone()) {
+                    Err(Some(reason))
+                } else {
+                    Err(None)
+                };
+            }
+        };
+
+        for _ in 0..allowed {
+            self.record_drop(chatter, tick, counts_towards_chat);
+        }
+        Ok(allowed)
+    }
+
+    /// Count a drop without checking the limits
+    fn record_drop(&mut self, chatter: &Chatter, tick: u32, counts_towards_chat: bool) {
+        self.told.remove(&chatter.user_id);
+        self.last_drop_at_tick.insert(chatter.user_id.clone(), tick);
+        *self
+            .drops_this_round
+            .entry(chatter.user_id.clone())
+            .or_insert(0) += 1;
+        if counts_towards_chat {
+            self.recent_drops.push_back(tick);
+        }
+    }
+
+    /// How many drops the chatter has room for right now, or why they can't drop at all
+    fn check(
+        &mut self,
+        chatter: &Chatter,
+        tick: u32,
+        config: &Config,
+        counts_towards_chat: bool,
+    ) -> Result<usize, String> {
+        let drops = &config.drops;
+        let drops_this_round = self
+            .drops_this_round
+            .get(&chatter.user_id)
+            .copied()
+            .unwrap_or(0);
+        if drops.per_round > 0 && drops_this_round >= drops.per_round {
+            return Err(format!(
+                "{} has used all {} of their drops this round",
+                chatter.name, drops.per_round
+            ));
+        }
+
+        let cooldown_ticks = config.ticks_in(drops.cooldown());
+        if let Some(last_drop_at_tick) = self.last_drop_at_tick.get(&chatter.user_id) {
+            let ticks_since_drop = tick - last_drop_at_tick;
+            if ticks_since_drop < cooldown_ticks {
+                return Err(format!(
+                    "{} can drop again in {}",
+                    chatter.name,
+                    Self::describe_wait(cooldown_ticks - ticks_since_drop, config)
+                ));
+            }
+        }
+
+        let room_this_round = if drops.per_round > 0 {
+            (drops.per_round - drops_this_round) as usize
+        } else {
+            usize::MAX
+        };
+        if !counts_towards_chat {
+            return Ok(room_this_round);
+        }
+
+        let window_ticks = config.world.framerate_target;
+        while let Some(drop_at_tick) = self.recent_drops.front() {
+            if tick - drop_at_tick < window_ticks {
+                break;
+            }
+            self.recent_drops.pop_front();
+        }
+        if drops.max_per_second > 0 && self.recent_drops.len() >= drops.max_per_second as usize {
+            let oldest_drop_at_tick = self.recent_drops.front().copied().unwrap_or(tick);
+            return Err(format!(
+                "Chat is dropping too fast, {} can drop again in {}",
+                chatter.name,
+                Self::describe_wait(window_ticks - (tick - oldest_drop_at_tick), config)
+            ));
+        }
+
+        let room_this_second = if drops.max_per_second > 0 {
+            drops.max_per_second as usize - self.recent_drops.len()
+        } else {
+            usize::MAX
+        };
+        Ok(room_this_round.min(room_this_second))
+    }
+
+    /// Rounded up to whole seconds so nobody is told to wait 0 seconds
+    fn describe_wait(ticks: u32, config: &Config) -> String {
+        let seconds = (ticks as f32 / config.world.framerate_target as f32).ceil() as u32;
+        if seconds == 1 {
+            "1 second".to_owned()
+        } else {
+            format!("{} seconds", seconds)
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+/// Turns however long each frame took into whole updates of the same length, so the game
+/// updates `framerate_target` times a second whether the monitor draws 30 or 144 frames.
+/// Whatever time is left over carries into the next frame, and how far it is into the next
+/// update tells drawing how far to move objects between where they were and where they are.
+#[derive(Debug)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulated: Duration,
+    /// a frame that took longer than this many updates lets the game fall behind instead of
+    /// catching up, otherwise a slow PC spends every frame catching up on the last one
+    max_steps_per_frame: u32,
+}
+
+impl FixedTimestep {
+    pub fn new(framerate_target: u32, max_steps_per_frame: u32) -> FixedTimestep {
+        FixedTimestep {
+            step: Duration::from_secs_f64(1.0 / framerate_target as f64),
+            accumulated: Duration::default(),
+            max_steps_per_frame,
+        }
+    }
+
+    pub fn add_frame_time(&mut self, frame_time: Duration) {
+        let max_accumulated = self.step * self.max_steps_per_frame;
+        self.accumulated = (self.accumulated + frame_time).min(max_accumulated);
+    }
+
+    /// Returns true once for every update that fits in the time added so far
+    pub fn take_step(&mut self) -> bool {
+        if self.accumulated >= self.step {
+            self.accumulated -= self.step;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// How far between the last update and the next one, from 0.0 to 1.0
+    pub fn get_alpha(&self) -> f32 {
+        (self.accumulated.as_secs_f64() / self.step.as_secs_f64()) as f32
+    }
+}
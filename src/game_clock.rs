@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+/// Game time, which stands still while the game is paused and can run slower or faster than
+/// real time. Everything timed in the game reads from here instead of the wall clock, so
+/// pausing or slowing down freezes or stretches the round's timer, how long objects have
+/// been alive, how long the streamer can't be hit for and how fast sprites animate all at once.
+///
+/// The simulation moves the clock forward every tick, tests can build their own clock and
+/// move it along with `advance` to hand systems exactly the time they want.
+#[derive(Debug, Clone)]
+pub struct GameClock {
+    framerate_target: u32,
+    time_scale: f32,
+    /// ticks that weren't paused
+    ticks: u32,
+    /// game time counted in ticks, so whole seconds stay whole however many ticks are added up
+    elapsed_ticks: f64,
+    delta: Duration,
+    is_paused: bool,
+}
+
+impl GameClock {
+    pub fn new(framerate_target: u32) -> GameClock {
+        GameClock {
+            framerate_target,
+            time_scale: 1.0,
+            ticks: 0,
+            elapsed_ticks: 0.0,
+            delta: Duration::default(),
+            is_paused: false,
+        }
+    }
+
+    /// Called once every update, one tick of game time is stretched by the time scale
+    pub fn tick(&mut self) {
+        if self.is_paused {
+            self.delta = Duration::default();
+        } else {
+            self.ticks += 1;
+            self.elapsed_ticks += self.time_scale as f64;
+            self.delta = self.ticks_to_duration(self.time_scale as f64);
+        }
+    }
+
+    /// Move the clock forward by exactly this much game time
+    pub fn advance(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed_ticks += delta.as_secs_f64() * self.framerate_target as f64;
+    }
+
+    pub fn set_paused(&mut self, is_paused: bool) {
+        self.is_paused = is_paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// 1.0 is real time, 0.5 is slow motion and 2.0 is twice as fast
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale;
+    }
+
+    pub fn get_time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// How many ticks the game wasn't paused for, whatever the time scale
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// How much game time has passed
+    pub fn get_elapsed(&self) -> Duration {
+        self.ticks_to_duration(self.elapsed_ticks)
+    }
+
+    /// How much game time passed in the last tick
+    pub fn get_delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// How much game time has passed since the clock read `elapsed`
+    pub fn get_time_since(&self, elapsed: Duration) -> Duration {
+        self.get_elapsed().checked_sub(elapsed).unwrap_or_default()
+    }
+
+    fn ticks_to_duration(&self, ticks: f64) -> Duration {
+        Duration::from_secs_f64(ticks / self.framerate_target as f64)
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{chatter::Chatter, running_state::RunningState};
+
+/// Something that happened inside the simulation that the outside world might want to react to,
+/// for example by playing a sound or sending a message to chat
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    SendToChat(String),
+    ObjectDropped(Chatter),
+    PlayerJumped,
+    PlayerHealed,
+    PlayerHit(Chatter),
+    RunningStateChanged(RunningState),
+    /// the game was unpaused and carries on in this state where it left off
+    RunningStateResumed(RunningState),
+}
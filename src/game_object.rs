@@ -1,14 +1,15 @@
-use super::{Chatter, DrawSystem, GameObjectType, LifeSystem, PhysicsSystem};
+use super::{Chatter, DrawSystem, GameObjectType, LifeSystem, PhysicsSystem, PlayerInput};
+use crate::game_clock::GameClock;
 use eyre::Result;
 use ggez::graphics::Rect;
 use ggez::nalgebra::Point2;
 use ggez::{Context, GameResult};
 
-const GRAVITY_FORCE: f32 = 0.3;
-
 #[derive(Debug)]
 pub struct GameObject {
     pub location: Rect,
+    /// where the object was before the last update, drawing moves it smoothly from here to `location`
+    previous_location: Rect,
     draw_system: Option<Box<dyn DrawSystem>>,
     physics_system: Option<Box<dyn PhysicsSystem>>,
     pub life_system: Option<Box<dyn LifeSystem>>,
@@ -16,6 +17,8 @@ pub struct GameObject {
     pub chatter: Option<Chatter>,
     rotation: f32,
     pub my_type: GameObjectType,
+    /// how many lives the player loses when hit by this object
+    pub damage: u8,
 }
 
 impl GameObject {
@@ -32,8 +35,10 @@ impl GameObject {
         my_type: GameObjectType,
         life_system: Option<Box<dyn LifeSystem>>,
     ) -> GameObject {
+        let location = Rect::new(x, y, width, height);
         GameObject {
-            location: Rect::new(x, y, width, height),
+            location,
+            previous_location: location,
             draw_system,
             physics_system,
             life_system,
@@ -41,25 +46,34 @@ impl GameObject {
             chatter,
             rotation: 0.0,
             my_type,
+            damage: 1,
         }
     }
 
+    pub fn with_damage(mut self, damage: u8) -> GameObject {
+        self.damage = damage;
+        self
+    }
+
     pub fn update(
         &mut self,
-        time_since_start: std::time::Duration,
+        clock: &GameClock,
         screen_size: (f32, f32),
-        context: &mut Context,
+        gravity_force: f32,
+        input: &PlayerInput,
         collidable_game_objects: &[GameObject],
     ) -> Result<()> {
+        self.previous_location = self.location;
         if let Some(physics_system) = &mut self.physics_system {
             physics_system.update(
                 &mut self.location,
                 screen_size,
-                GRAVITY_FORCE,
-                context,
+                gravity_force,
+                input,
                 collidable_game_objects,
                 &mut self.rotation,
                 &mut self.life_system,
+                clock,
             )?;
         }
 
@@ -69,21 +83,26 @@ impl GameObject {
             } else {
                 0.0
             };
-            draw_system.update(time_since_start, velocity_x);
+            draw_system.update(clock, velocity_x);
         }
 
         if let Some(life_system) = &mut self.life_system {
-            life_system.update(screen_size, &self.location);
+            life_system.update(screen_size, &self.location, clock);
         }
 
         Ok(())
     }
 
-    pub fn draw(&self, context: &mut Context) -> GameResult<()> {
+    /// Alpha is how far the game is between the last update and the next one
+    pub fn draw(&self, context: &mut Context, alpha: f32) -> GameResult<()> {
         if let Some(draw_system) = &self.draw_system {
+            let previous = &self.previous_location;
             draw_system.draw(
                 context,
-                Point2::new(self.location.x, self.location.y),
+                Point2::new(
+                    previous.x + (self.location.x - previous.x) * alpha,
+                    previous.y + (self.location.y - previous.y) * alpha,
+                ),
                 &self.rotation,
             )?;
         }
@@ -112,12 +131,14 @@ impl Clone for GameObject {
     fn clone(&self) -> Self {
         GameObject {
             location: self.location,
+            previous_location: self.previous_location,
             draw_system: None,
             physics_system: None,
             collidable: self.collidable,
             chatter: self.chatter.clone(),
             rotation: self.rotation,
             my_type: self.my_type.clone(),
+            damage: self.damage,
             life_system: None,
         }
     }
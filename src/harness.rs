@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use crate::{
+    chatter::Chatter, config::Config, game_event::GameEvent, game_object::GameObject,
+    game_object_type::GameObjectType, get_arena_size, player_input::PlayerInput,
+    running_state::RunningState, score::Score, simulation::Simulation, WINDOW_SIZE,
+};
+
+/// Plays the game without a window for tests. Chat messages and keys are handed to the
+/// simulation whenever the test wants and time only moves when the test runs ticks.
+pub struct Harness {
+    simulation: Simulation,
+    input: PlayerInput,
+    events: Vec<GameEvent>,
+}
+
+impl Harness {
+    pub fn new(config: Config, seed: u64) -> Harness {
+        Harness {
+            simulation: Simulation::new(get_arena_size(WINDOW_SIZE), None, config, seed),
+            input: PlayerInput::default(),
+            events: vec![],
+        }
+    }
+
+    /// A harness that has already run through the splash screen, ready for chat to drop things
+    pub fn started(config: Config, seed: u64) -> Harness {
+        let mut harness = Harness::new(config, seed);
+        harness.start_round();
+        harness
+    }
+
+    /// Send a message to the game from a chatter with the default color who isn't subscribed
+    pub fn chat(&mut self, name: &str, message: &str) {
+        self.chat_as(Chatter::new(name.to_owned(), (0, 0, 0), false), message);
+    }
+
+    pub fn chat_as(&mut self, chatter: Chatter, message: &str) {
+        self.simulation.handle_chat_message(message, chatter);
+    }
+
+    /// Pretend these scores were saved from earlier rounds, keyed by the chatters' user ids
+    pub fn set_high_scores(&mut self, high_scores: HashMap<String, Score>) {
+        self.simulation.set_high_scores(high_scores);
+    }
+
+    /// The keys held down from now on until the input is set again
+    pub fn set_input(&mut self, input: PlayerInput) {
+        self.input = input;
+    }
+
+    pub fn get_input(&self) -> PlayerInput {
+        self.input
+    }
+
+    pub fn run_ticks(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// Drop a fire on the streamer, another one this many ticks later and wait twice as long
+    /// again, returning how many lives the streamer lost to them
+    pub fn count_lives_lost_to_two_fires(&mut self, ticks_apart: u32) -> u8 {
+        let lives = self.get_player_lives_left();
+        let fire = format!("#fire {}", self.get_player_column());
+        self.chat("alice", &fire);
+        self.run_ticks(ticks_apart);
+        self.chat("bob", &fire);
+        self.run_ticks(ticks_apart * 2);
+        lives - self.get_player_lives_left()
+    }
+
+    /// Run through the break after the last round and the splash screen until chat can start playing
+    pub fn start_round(&mut self) {
+        while !matches!(
+            self.simulation.get_running_state(),
+            RunningState::Playing | RunningState::Paused
+        ) && !self.simulation.is_session_over()
+        {
+            self.tick();
+        }
+    }
+
+    /// Keep running until chat or the streamer wins, which happens when the round's timer runs out at the latest
+    pub fn finish_round(&mut self) {
+        while !self.simulation.get_running_state().is_game_over() {
+            self.tick();
+        }
+    }
+
+    pub fn get_running_state(&self) -> RunningState {
+        self.simulation.get_running_state()
+    }
+
+    pub fn get_player_lives_left(&self) -> u8 {
+        self.simulation.get_player_lives_left()
+    }
+
+    pub fn get_game_objects(&self) -> &[GameObject] {
+        self.simulation.get_game_objects()
+    }
+
+    pub fn count_game_objects(&self, game_object_type: GameObjectType) -> usize {
+        self.get_game_objects()
+            .iter()
+            .filter(|game_object| game_object.my_type == game_object_type)
+            .count()
+    }
+
+    /// The points scored this round by the chatter with the given display name
+    pub fn get_score(&self, name: &str) -> u128 {
+        self.simulation
+            .get_scores()
+            .values()
+            .find(|score| score.name == name)
+            .map(|score| score.points)
+            .unwrap_or(0)
+    }
+
+    /// Everything the game has reported since the harness was created
+    pub fn get_events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    /// Everything the game has said to chat since the harness was created
+    pub fn get_chat_log(&self) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::SendToChat(message) => Some(message.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn get_simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    /// The drop column the middle of the streamer is in
+    fn get_player_column(&self) -> u8 {
+        let drop_zone_count = self.simulation.get_config().world.drop_zone_count;
+        let column_width = get_arena_size(WINDOW_SIZE).0 / drop_zone_count as f32;
+        self.get_game_objects()
+            .iter()
+            .find(|game_object| game_object.my_type == GameObjectType::Player)
+            .map(|player| ((player.location.x + player.location.w / 2.0) / column_width) as u8)
+            .unwrap_or(0)
+    }
+
+    fn tick(&mut self) {
+        let events = self.simulation.update(&self.input);
+        self.events.extend(events);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::score::Score;
+
+const HIGH_SCORES_VERSION: u32 = 2;
+const HIGH_SCORES_FILE_NAME: &str = "high_scores.json";
+const BACKUP_FILE_NAME: &str = "high_scores.json.bak";
+const TEMPORARY_FILE_NAME: &str = "high_scores.json.tmp";
+/// Before the high scores were versioned they were saved as `name:points` lines
+const LEGACY_FILE_NAME: &str = "high_scores";
+
+#[derive(Serialize, Deserialize)]
+struct HighScoresFile<T> {
+    version: u32,
+    scores: Vec<T>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HighScore {
+    /// Scores saved before version 2 only know the chatter's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_id: Option<String>,
+    name: String,
+    points: u128,
+}
+
+/// Every chatter's points added up over all of the rounds they have played
+#[derive(Debug)]
+pub struct HighScores {
+    folder: PathBuf,
+    /// keyed by the chatters' user ids
+    scores: HashMap<String, Score>,
+    /// Scores from before chatters were known by their user id, keyed by their lowercase name.
+    /// They are merged into the chatter's scores the next time they play.
+    unclaimed_scores: HashMap<String, Score>,
+    warnings: Vec<String>,
+}
+
+impl HighScores {
+    /// Load the high scores saved in the folder, falling back to the backup if the file is broken
+    /// and moving the scores over from the old format if there is nothing newer.
+    /// Entries that can't be read are skipped and listed in the warnings, an error means nothing
+    /// could be loaded and saving would overwrite scores that might still be recovered.
+    pub fn load(folder: &Path) -> Result<HighScores> {
+        let mut high_scores = HighScores {
+            folder: folder.to_path_buf(),
+            scores: HashMap::new(),
+            unclaimed_scores: HashMap::new(),
+            warnings: vec![],
+        };
+
+        match fs::read_to_string(folder.join(HIGH_SCORES_FILE_NAME)) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(high_scores_file) => high_scores.add_entries(high_scores_file)?,
+                Err(error) => {
+                    high_scores.warnings.push(format!(
+                        "{} is broken, loading the backup instead: {}",
+                        HIGH_SCORES_FILE_NAME, error
+                    ));
+                    let backup = fs::read_to_string(folder.join(BACKUP_FILE_NAME)).map_err(
+                        |backup_error| {
+                            eyre!(
+                                "{} is broken ({}) and there is no backup: {}",
+                                HIGH_SCORES_FILE_NAME,
+                                error,
+                                backup_error
+                            )
+                        },
+                    )?;
+                    high_scores.add_entries(serde_json::from_str(&backup)?)?;
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                match fs::read_to_string(folder.join(LEGACY_FILE_NAME)) {
+                    Ok(contents) => high_scores.parse_legacy(&contents),
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(high_scores)
+    }
+
+    /// Add a round's scores, keyed by the chatters' user ids
+    pub fn add_scores(&mut self, scores: &HashMap<String, Score>) {
+        for (user_id, score) in scores {
+            let unclaimed_points = self
+                .unclaimed_scores
+                .remove(&score.name.to_lowercase())
+                .map(|unclaimed_score| unclaimed_score.points)
+                .unwrap_or(0);
+            let high_score = self
+                .scores
+                .entry(user_id.to_owned())
+                .or_insert_with(|| Score::new(score.name.clone(), 0));
+            high_score.name = score.name.clone();
+            high_score.points += score.points + unclaimed_points;
+        }
+    }
+
+    /// The high scores keyed by the chatters' user ids
+    pub fn get_scores(&self) -> &HashMap<String, Score> {
+        &self.scores
+    }
+
+    /// Anything that went wrong while loading that didn't stop the high scores from loading
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Write the scores to a temporary file first and then move it into place so a crash
+    /// can't leave half written scores behind. The scores that were there before are kept as a backup.
+    pub fn save(&self) -> Result<()> {
+        let claimed_scores = self
+            .scores
+            .iter()
+            .map(|(user_id, score)| (Some(user_id), score));
+        let unclaimed_scores = self.unclaimed_scores.values().map(|score| (None, score));
+        let mut scores: Vec<HighScore> = claimed_scores
+            .chain(unclaimed_scores)
+            .map(|(user_id, score)| HighScore {
+                user_id: user_id.cloned(),
+                name: score.name.clone(),
+                points: score.points,
+            })
+            .collect();
+        scores.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+        let high_scores_file = HighScoresFile {
+            version: HIGH_SCORES_VERSION,
+            scores,
+        };
+
+        fs::create_dir_all(&self.folder)?;
+        let path = self.folder.join(HIGH_SCORES_FILE_NAME);
+        let temporary_path = self.folder.join(TEMPORARY_FILE_NAME);
+        let mut temporary_file = File::create(&temporary_path)?;
+        serde_json::to_writer_pretty(&mut temporary_file, &high_scores_file)?;
+        temporary_file.write_all(b"\n")?;
+        temporary_file.sync_all()?;
+
+        if path.exists() {
+            fs::copy(&path, self.folder.join(BACKUP_FILE_NAME))?;
+        }
+        fs::rename(temporary_path, path)?;
+        Ok(())
+    }
+
+    /// Each entry is read on its own so one broken entry doesn't lose everyone else's scores
+    fn add_entries(&mut self, high_scores_file: HighScoresFile<serde_json::Value>) -> Result<()> {
+        if high_scores_file.version > HIGH_SCORES_VERSION {
+            return Err(eyre!(
+                "the high scores were saved by a newer version of the game (version {})",
+                high_scores_file.version
+            ));
+        }
+
+        for (index, entry) in high_scores_file.scores.into_iter().enumerate() {
+            match serde_json::from_value::<HighScore>(entry) {
+                Ok(high_score) => self.add_high_score(high_score),
+                Err(error) => {
+                    self.warnings
+                        .push(format!("skipped high score entry {}: {}", index + 1, error))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_legacy(&mut self, contents: &str) {
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.rsplitn(2, ':');
+            let points = parts.next().and_then(|points| points.parse::<u128>().ok());
+            match (parts.next(), points) {
+                (Some(name), Some(points)) if !name.is_empty() => self.add_high_score(HighScore {
+                    user_id: None,
+                    name: name.to_owned(),
+                    points,
+                }),
+                _ => self.warnings.push(format!(
+                    "skipped line {} of the old high scores file: '{}'",
+                    index + 1,
+                    line
+                )),
+            }
+        }
+    }
+
+    /// Scores without a user id are kept by name, chatters who changed the capitalization
+    /// of their name end up with their scores together
+    fn add_high_score(&mut self, high_score: HighScore) {
+        let HighScore {
+            user_id,
+            name,
+            points,
+        } = high_score;
+        let (scores, key) = match user_id {
+            Some(user_id) => (&mut self.scores, user_id),
+            None => (&mut self.unclaimed_scores, name.to_lowercase()),
+        };
+        scores
+            .entry(key)
+            .or_insert_with(|| Score::new(name, 0))
+            .points += points;
+    }
+}
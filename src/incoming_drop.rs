@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use crate::{command::Command, game_clock::GameClock};
+
+/// A drop that was let through and is about to land. Its drop zone warns the streamer
+/// until then, so the column is picked when the warning starts rather than when it lands.
+pub struct IncomingDrop {
+    pub command: Command,
+    pub column: u8,
+    /// in game time
+    lands_at: Duration,
+}
+
+impl IncomingDrop {
+    pub fn new(command: Command, column: u8, clock: &GameClock, warning: Duration) -> IncomingDrop {
+        IncomingDrop {
+            command,
+            column,
+            lands_at: clock.get_elapsed() + warning,
+        }
+    }
+
+    pub fn get_time_left(&self, clock: &GameClock) -> Duration {
+        self.lands_at
+            .checked_sub(clock.get_elapsed())
+            .unwrap_or_default()
+    }
+
+    pub fn has_landed(&self, clock: &GameClock) -> bool {
+        clock.get_elapsed() >= self.lands_at
+    }
+}
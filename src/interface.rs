@@ -1,4 +1,16 @@
-use crate::running_state::RunningState;
+use crate::{
+    chatter::Chatter,
+    command::{Command, DropTarget},
+    game_clock::GameClock,
+    incoming_drop::IncomingDrop,
+    object_registry::ObjectRegistry,
+    player_input::PlayerInput,
+    running_state::RunningState,
+    score::POINTS_FOR_HIT,
+    sidebar::Sidebar,
+    sprites::Sprites,
+    DROP_ZONE_HEIGHT,
+};
 
 use super::GameObject;
 use eyre::Result;
@@ -6,21 +18,29 @@ use ggez::graphics::{
     Align, Color, DrawMode, DrawParam, Font, Mesh, MeshBuilder, Rect, Scale, Text,
 };
 use ggez::nalgebra::Point2;
-use ggez::{graphics, timer, Context, GameResult};
+use ggez::{graphics, Context, GameResult};
 use graphics::Image;
+use std::collections::{HashMap, VecDeque};
 
-const DROP_ZONE_HEIGHT: f32 = 50.0;
 const GAME_OVER_FONT_SIZE: f32 = 150.0;
+const INCOMING_DROP_NAME_SIZE: f32 = 20.0;
+const DROP_PANEL_TITLE_SIZE: f32 = 24.0;
+const DROP_PANEL_ROW_HEIGHT: f32 = 28.0;
+const DROP_PANEL_TEXT_SIZE: f32 = 20.0;
+const MAX_UPCOMING_DROPS_SHOWN: usize = 15;
+/// longer names are cut short so they stay on one line
+const MAX_DROP_PANEL_NAME_LENGTH: usize = 10;
 
 pub struct Interface {
     pub width: f32,
     screen_size: (f32, f32),
     drop_zones: Vec<Rect>,
     drop_zone_background: Mesh,
+    drop_zone_warning: Mesh,
     drop_zone_labels: Vec<Text>,
     single_drop_zone_width: f32,
     game_objects: Vec<GameObject>,
-    instruction_image: Image,
+    sidebar: Sidebar,
     heart_image: Image,
     player_lives_left: u8,
     full_mask: Mesh,
@@ -31,16 +51,19 @@ impl Interface {
         context: &mut Context,
         screen_size: (f32, f32),
         player_lives_left: u8,
+        drop_zone_count: u8,
+        objects: &ObjectRegistry,
+        sprites: &Sprites,
     ) -> GameResult<Interface> {
-        let instruction_image = Image::new(context, "/sidebar.png")?;
-        let width = instruction_image.width().into();
+        let sidebar = Sidebar::new(context, screen_size, objects, sprites, drop_zone_count)?;
+        let width = sidebar.width;
         let (screen_width, screen_height) = screen_size;
         let mut drop_zones = vec![];
         let drop_zone_width = screen_width - width;
-        let single_drop_zone_width = drop_zone_width / crate::DROP_ZONE_COUNT as f32;
+        let single_drop_zone_width = drop_zone_width / drop_zone_count as f32;
         let mut drop_zone_labels = vec![];
 
-        for count in 0..crate::DROP_ZONE_COUNT {
+        for count in 0..drop_zone_count {
             let drop_zone = Rect::new(
                 count as f32 * single_drop_zone_width,
                 0.0,
@@ -62,6 +85,9 @@ impl Interface {
         let drop_zone_background = MeshBuilder::new()
             .rectangle(DrawMode::stroke(1.0), drop_zones[0], graphics::WHITE)
             .build(context)?;
+        let drop_zone_warning = MeshBuilder::new()
+            .rectangle(DrawMode::fill(), drop_zones[0], graphics::WHITE)
+            .build(context)?;
 
         let mut game_over_text = Text::new("Game Over!");
         game_over_text.set_font(Font::default(), Scale::uniform(GAME_OVER_FONT_SIZE));
@@ -75,32 +101,18 @@ impl Interface {
             )
             .build(context)?;
 
-        let mut attack_subtitle = Text::new("Attack the Streamer with following commands:");
-        attack_subtitle.set_font(Font::default(), Scale::uniform(30.0));
-        attack_subtitle.set_bounds(Point2::new(width, 60.0), Align::Center);
-
-        let mut help_subtitle = Text::new("Help the Streamer with following commands:");
-        help_subtitle.set_font(Font::default(), Scale::uniform(30.0));
-        help_subtitle.set_bounds(Point2::new(width, 60.0), Align::Center);
-
-        let mut player_lives_left_subtitle = Text::new("Streamer Lives Left:");
-        player_lives_left_subtitle.set_font(Font::default(), Scale::uniform(30.0));
-        player_lives_left_subtitle.set_bounds(Point2::new(width, 60.0), Align::Center);
-
         let heart_image = Image::new(context, "/heart.png")?;
 
-        let mut game_over_title = Text::new("Game Over!");
-        game_over_title.set_font(Font::default(), Scale::uniform(50.0));
-
         Ok(Interface {
             width,
             screen_size,
             drop_zones,
             drop_zone_background,
+            drop_zone_warning,
             drop_zone_labels,
             single_drop_zone_width,
             game_objects: vec![],
-            instruction_image,
+            sidebar,
             heart_image,
             player_lives_left,
             full_mask,
@@ -111,14 +123,11 @@ impl Interface {
         context: &mut Context,
         screen_size: (f32, f32),
         running_state: &RunningState,
+        alpha: f32,
     ) -> GameResult<()> {
         self.draw_drop_zones(context)?;
 
-        graphics::draw(
-            context,
-            &self.instruction_image,
-            DrawParam::new().dest(Point2::new(screen_size.0 - self.width, 0.0)),
-        )?;
+        self.sidebar.draw(context)?;
 
         let mut heart_x = screen_size.0
             - (self.width / 2.0)
@@ -127,14 +136,14 @@ impl Interface {
             graphics::draw(
                 context,
                 &self.heart_image,
-                DrawParam::new().dest(Point2::new(heart_x, 1015.0)),
+                DrawParam::new().dest(Point2::new(heart_x, self.sidebar.hearts_y)),
             )?;
 
             heart_x += self.heart_image.width() as f32 + 5.0;
         }
 
         for obj in &self.game_objects {
-            obj.draw(context)?;
+            obj.draw(context, alpha)?;
         }
 
         if running_state.is_game_over() {
@@ -173,30 +182,192 @@ impl Interface {
         Ok(())
     }
 
-    /// Take in an index like 3
-    /// which should return the middle x,y coordinates of the corresponding drop zone
-    pub fn get_column_coordinates_by_index(&self, index: u8) -> Point2<f32> {
-        Point2::new(
-            index as f32 * self.single_drop_zone_width + self.single_drop_zone_width / 2.0,
-            DROP_ZONE_HEIGHT / 2.0,
-        )
+    /// Light up the drop zones objects are about to land in, with the object and who sent it
+    pub fn draw_incoming_drops(
+        &self,
+        context: &mut Context,
+        incoming_drops: &[IncomingDrop],
+        sprites: Option<&Sprites>,
+        clock: &GameClock,
+    ) -> GameResult<()> {
+        // names of drops coming into the same column are listed under each other
+        let mut names_in_column: HashMap<u8, usize> = HashMap::new();
+        for incoming_drop in incoming_drops {
+            let command = &incoming_drop.command;
+            let drop_zone_x = incoming_drop.column as f32 * self.single_drop_zone_width;
+            let time_left = incoming_drop.get_time_left(clock).as_secs_f32();
+            let flash = 0.4 + 0.2 * (time_left * 12.0).sin();
+            graphics::draw(
+                context,
+                &self.drop_zone_warning,
+                DrawParam::new()
+                    .dest(Point2::new(drop_zone_x, 0.0))
+                    .color(Color::new(1.0, 0.2, 0.2, flash)),
+            )?;
+
+            if let Some(sprite) =
+                sprites.and_then(|sprites| sprites.get_object(&command.object.name))
+            {
+                let scale = DROP_ZONE_HEIGHT * 0.8 / sprite.width.max(sprite.height);
+                sprite.draw(
+                    context,
+                    Point2::new(drop_zone_x + 5.0, DROP_ZONE_HEIGHT * 0.1),
+                    [scale, scale],
+                    &0.0,
+                    None,
+                )?;
+            }
+
+            let names_above = names_in_column.entry(incoming_drop.column).or_insert(0);
+            let name_color = if command.chatter.is_subscriber {
+                command.chatter.get_readable_color()
+            } else {
+                graphics::WHITE
+            };
+            let mut name = Text::new(command.chatter.name.as_str());
+            name.set_font(Font::default(), Scale::uniform(INCOMING_DROP_NAME_SIZE));
+            name.set_bounds(
+                Point2::new(self.single_drop_zone_width, INCOMING_DROP_NAME_SIZE),
+                Align::Center,
+            );
+            graphics::draw(
+                context,
+                &name,
+                DrawParam::new()
+                    .dest(Point2::new(
+                        drop_zone_x,
+                        DROP_ZONE_HEIGHT + *names_above as f32 * INCOMING_DROP_NAME_SIZE,
+                    ))
+                    .color(name_color),
+            )?;
+            *names_above += 1;
+        }
+
+        Ok(())
+    }
+
+    /// List the drops that are about to land on the left of the room the sidebar leaves under
+    /// the commands, and the chatters that hit the streamer last on the right, so chat can see
+    /// their drops were let through
+    pub fn draw_drop_panel(
+        &self,
+        context: &mut Context,
+        upcoming_drops: &[(&Command, DropTarget)],
+        recent_hits: &VecDeque<Chatter>,
+        sprites: Option<&Sprites>,
+    ) -> GameResult<()> {
+        let area = self.sidebar.drop_panel_area;
+        let column_width = area.w / 2.0 - 20.0;
+        let upcoming_x = area.x + 20.0;
+        let recent_hits_x = area.x + area.w / 2.0;
+        // a row under the title is kept for saying how many more drops there are
+        let rows = ((area.h / DROP_PANEL_ROW_HEIGHT) as usize).saturating_sub(1);
+        let upcoming_shown = MAX_UPCOMING_DROPS_SHOWN.min(rows.saturating_sub(1));
+
+        self.draw_drop_panel_title(context, "Next drops", upcoming_x, area.y, column_width)?;
+        let mut row_y = area.y + DROP_PANEL_ROW_HEIGHT;
+        for (command, target) in upcoming_drops.iter().take(upcoming_shown) {
+            let sprite = sprites.and_then(|sprites| sprites.get_object(&command.object.name));
+            if let Some(sprite) = sprite {
+                let scale = DROP_PANEL_TEXT_SIZE / sprite.width.max(sprite.height);
+                sprite.draw(
+                    context,
+                    Point2::new(upcoming_x, row_y),
+                    [scale, scale],
+                    &0.0,
+                    None,
+                )?;
+            }
+            let row = format!("{} {}", target, Self::shorten_name(&command.chatter.name));
+            self.draw_drop_panel_row(
+                context,
+                &row,
+                command.chatter.get_readable_color(),
+                Point2::new(upcoming_x + DROP_PANEL_TEXT_SIZE + 5.0, row_y),
+            )?;
+            row_y += DROP_PANEL_ROW_HEIGHT;
+        }
+        if upcoming_drops.len() > upcoming_shown && rows > 0 {
+            let more = format!("and {} more", upcoming_drops.len() - upcoming_shown);
+            self.draw_drop_panel_row(
+                context,
+                &more,
+                graphics::WHITE,
+                Point2::new(upcoming_x, row_y),
+            )?;
+        }
+
+        self.draw_drop_panel_title(context, "Recent hits", recent_hits_x, area.y, column_width)?;
+        let mut row_y = area.y + DROP_PANEL_ROW_HEIGHT;
+        for chatter in recent_hits.iter().take(rows) {
+            let row = format!("{} +{}", Self::shorten_name(&chatter.name), POINTS_FOR_HIT);
+            self.draw_drop_panel_row(
+                context,
+                &row,
+                chatter.get_readable_color(),
+                Point2::new(recent_hits_x, row_y),
+            )?;
+            row_y += DROP_PANEL_ROW_HEIGHT;
+        }
+
+        Ok(())
+    }
+
+    fn draw_drop_panel_title(
+        &self,
+        context: &mut Context,
+        title: &str,
+        x: f32,
+        y: f32,
+        width: f32,
+    ) -> GameResult<()> {
+        let mut title = Text::new(title);
+        title.set_font(Font::default(), Scale::uniform(DROP_PANEL_TITLE_SIZE));
+        title.set_bounds(Point2::new(width, DROP_PANEL_ROW_HEIGHT), Align::Left);
+        graphics::draw(context, &title, DrawParam::new().dest(Point2::new(x, y)))
+    }
+
+    fn draw_drop_panel_row(
+        &self,
+        context: &mut Context,
+        row: &str,
+        color: Color,
+        location: Point2<f32>,
+    ) -> GameResult<()> {
+        let mut row = Text::new(row);
+        row.set_font(Font::default(), Scale::uniform(DROP_PANEL_TEXT_SIZE));
+        graphics::draw(context, &row, DrawParam::new().dest(location).color(color))
+    }
+
+    fn shorten_name(name: &str) -> String {
+        if name.chars().count() > MAX_DROP_PANEL_NAME_LENGTH {
+            let short_name: String = name.chars().take(MAX_DROP_PANEL_NAME_LENGTH - 1).collect();
+            format!("{}…", short_name)
+        } else {
+            name.to_owned()
+        }
     }
 
     pub fn add_game_object(&mut self, game_object: GameObject) {
         self.game_objects.push(game_object);
     }
 
-    pub fn update(&mut self, context: &mut Context, player_lives_left: u8) -> Result<()> {
-        let time_since_start = timer::time_since_start(context);
+    /// Get rid of the timers from the last round when a new one starts
+    pub fn clear_game_objects(&mut self) {
+        self.game_objects.clear();
+    }
+
+    pub fn update(&mut self, clock: &GameClock, player_lives_left: u8) -> Result<()> {
         let screen_size = self.screen_size;
         let collidable_game_objects = vec![];
         self.player_lives_left = player_lives_left;
 
         self.game_objects.iter_mut().try_for_each(|game_object| {
             game_object.update(
-                time_since_start,
+                clock,
                 screen_size,
-                context,
+                0.0,
+                &PlayerInput::default(),
                 &collidable_game_objects,
             )
         })?;
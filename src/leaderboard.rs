@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use crate::{chatter::Chatter, config::Config, score::Score};
+
+const DEFAULT_TOP_COUNT: usize = 3;
+const MAX_TOP_COUNT: usize = 10;
+
+/// Answers chatters asking about scores with `!score`, `!rank` and `!top 5`, counting
+/// both the saved high scores and the points from the round being played.
+/// Answers are limited so a busy chat asking over and over doesn't flood itself.
+#[derive(Debug, Default)]
+pub struct Leaderboard {
+    /// keyed by the chatters' user ids
+    high_scores: HashMap<String, Score>,
+    /// when each chatter was last answered for each command
+    answered_at_tick: HashMap<(String, &'static str), u32>,
+    last_answer_at_tick: Option<u32>,
+}
+
+impl Leaderboard {
+    pub fn set_high_scores(&mut self, high_scores: HashMap<String, Score>) {
+        self.high_scores = high_scores;
+    }
+
+    /// Returns what to tell chat, or `None` if the message isn't a leaderboard command
+    /// or asked too soon
+    pub fn handle_chat_message(
+        &mut self,
+        message: &str,
+        chatter: &Chatter,
+        round_scores: &HashMap<String, Score>,
+        tick: u32,
+        config: &Config,
+    ) -> Option<String> {
+        let mut parts = message.split_whitespace();
+        let command = match parts.next()?.to_lowercase().as_str() {
+            "!score" => "!score",
+            "!rank" => "!rank",
+            "!top" => "!top",
+            _ => return None,
+        };
+
+        if let Some(last_answer_at_tick) = self.last_answer_at_tick {
+            if tick - last_answer_at_tick < config.ticks_in(config.chat.leaderboard_interval()) {
+                return None;
+            }
+        }
+        let key = (chatter.user_id.clone(), command);
+        if let Some(answered_at_tick) = self.answered_at_tick.get(&key) {
+            if tick - answered_at_tick < config.ticks_in(config.chat.leaderboard_cooldown()) {
+                return None;
+            }
+        }
+        self.answered_at_tick.insert(key, tick);
+        self.last_answer_at_tick = Some(tick);
+
+        let totals = self.get_totals(round_scores);
+        let answer = match command {
+            "!score" => Self::get_score_answer(chatter, round_scores, &totals),
+            "!rank" => Self::get_rank_answer(chatter, &totals),
+            _ => {
+                let count = parts
+                    .next()
+                    .and_then(|count| count.parse().ok())
+                    .unwrap_or(DEFAULT_TOP_COUNT)
+                    .clamp(1, MAX_TOP_COUNT);
+                Self::get_top_answer(count, &totals)
+            }
+        };
+        Some(answer)
+    }
+
+    /// Everyone's high score with this round's points added, highest first
+    fn get_totals(&self, round_scores: &HashMap<String, Score>) -> Vec<(String, Score)> {
+        let mut totals = self.high_scores.clone();
+        for (user_id, score) in round_scores {
+            let total = totals
+                .entry(user_id.to_owned())
+                .or_insert_with(|| Score::new(score.name.clone(), 0));
+            total.name = score.name.clone();
+            total.points += score.points;
+        }
+        let mut totals: Vec<(String, Score)> = totals.into_iter().collect();
+        totals.sort_by(|(_, a), (_, b)| b.points.cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+        totals
+    }
+
+    fn get_score_answer(
+        chatter: &Chatter,
+        round_scores: &HashMap<String, Score>,
+        totals: &[(String, Score)],
+    ) -> String {
+        let round_points = round_scores
+            .get(&chatter.user_id)
+            .map(|score| score.points)
+            .unwrap_or(0);
+        match totals
+            .iter()
+            .find(|(user_id, _)| *user_id == chatter.user_id)
+        {
+            Some((_, total)) => format!(
+                "{} has {} points this round and {} in total",
+                chatter.name, round_points, total.points
+            ),
+            None => format!("{} hasn't scored any points yet", chatter.name),
+        }
+    }
+
+    fn get_rank_answer(chatter: &Chatter, totals: &[(String, Score)]) -> String {
+        match totals
+            .iter()
+            .position(|(user_id, _)| *user_id == chatter.user_id)
+        {
+            Some(index) => format!(
+                "{} is ranked {} of {} with {} points",
+                chatter.name,
+                index + 1,
+                totals.len(),
+                totals[index].1.points
+            ),
+            None => format!(
+                "{} isn't ranked yet, drop something on the streamer to get on the leaderboard",
+                chatter.name
+            ),
+        }
+    }
+
+    fn get_top_answer(count: usize, totals: &[(String, Score)]) -> String {
+        if totals.is_empty() {
+            return "Nobody has scored any points yet".to_owned();
+        }
+        let top: Vec<String> = totals
+            .iter()
+            .take(count)
+            .enumerate()
+            .map(|(index, (_, score))| format!("{}. {} ({})", index + 1, score.name, score.points))
+            .collect();
+        format!("Top {}: {}", top.len(), top.join(", "))
+    }
+}
@@ -1,152 +1,221 @@
+mod chat_backend;
 mod chatter;
 pub mod command;
+mod command_queue;
+mod config;
+mod control_command;
 mod credits;
 mod draw_system;
+mod drop_limiter;
+mod fixed_timestep;
+mod game_clock;
+mod game_event;
 mod game_object;
 mod game_object_type;
+mod harness;
+mod high_scores;
+mod incoming_drop;
 mod interface;
+mod leaderboard;
 mod life_system;
+mod lobby;
+mod object_registry;
 mod physics;
+mod player_input;
+mod replay;
 mod running_state;
+mod scenario;
+mod score;
+mod sidebar;
+mod simulation;
 mod splash;
 mod sprites;
 mod utilities;
 
-use chatter::Chatter;
-use command::Command;
+pub use chat_backend::{
+    ChatBackend, ChatMessage, ConsoleChatBackend, FakeIrcServer, TwitchChatBackend, TwitchSettings,
+};
+pub use chatter::{Chatter, ChatterRole};
+pub use command_queue::CommandQueueMetrics;
+pub use config::{Config, OverflowPolicy};
 use credits::Credits;
-use draw_system::{DrawSystem, PlayerDrawSystem, TimerDrawSystem};
-use game_object::GameObject;
-use game_object_type::GameObjectType;
+use draw_system::{DrawSystem, TimerDrawSystem};
+pub use fixed_timestep::FixedTimestep;
+pub use game_clock::GameClock;
+pub use game_event::GameEvent;
+pub use game_object::GameObject;
+pub use game_object_type::GameObjectType;
 use ggez::audio;
 use ggez::audio::SoundSource;
 use ggez::event::EventHandler;
 use ggez::graphics::BLACK;
-use ggez::{graphics, timer, Context, GameResult};
+use ggez::input::keyboard::{self, KeyCode, KeyMods};
+use ggez::{filesystem, graphics, timer, Context, GameResult};
+pub use harness::Harness;
+pub use high_scores::HighScores;
+pub use incoming_drop::IncomingDrop;
 use interface::Interface;
-use life_system::{LifeSystem, PlayerLifeSystem};
-use physics::{PhysicsSystem, PlayerPhysics, TimerPhysicsSystem};
-use running_state::RunningState;
+use life_system::LifeSystem;
+pub use object_registry::{CommandHelp, ObjectBehavior, ObjectDefinition, ObjectRegistry};
+use physics::{PhysicsSystem, TimerPhysicsSystem};
+pub use player_input::PlayerInput;
+pub use replay::{Replay, ReplayRecorder};
+pub use running_state::RunningState;
+pub use scenario::Scenario;
+pub use score::Score;
+use sidebar::Sidebar;
+pub use simulation::Simulation;
 use splash::Splash;
-use sprites::Sprite;
+use sprites::{Sprite, Sprites};
+use std::io::BufWriter;
 use std::{collections::HashMap, time::Duration};
-use std::{
-    sync::mpsc::{channel, Receiver, Sender},
-    time::Instant,
-};
-use twitch_chat_wrapper::chat_message::ChatMessage;
 
-pub const DROP_ZONE_COUNT: u8 = 10;
-const GAME_TIME: Duration = Duration::from_secs(120);
-pub const SPLASH_DURATION: Duration = Duration::from_secs(15);
-const LIVES: u8 = 3;
-const FRAMERATE_TARGET: u32 = 60;
-const SCORES_FILE_NAME: &str = "/high_scores";
+pub const DROP_ZONE_HEIGHT: f32 = 50.0;
+pub const WINDOW_SIZE: (f32, f32) = (1920.0, 1080.0);
+/// how many of the chatters that joined are listed on each line of the splash screen
+const LOBBY_NAMES_PER_LINE: usize = 5;
+const MAX_LOBBY_NAMES: usize = 20;
+
+/// The room left for the arena in a window this big once the sidebar takes its share
+pub fn get_arena_size(screen_size: (f32, f32)) -> (f32, f32) {
+    (
+        screen_size.0 - Sidebar::width_for(screen_size.0),
+        screen_size.1,
+    )
+}
 
+/// Where chat messages and the streamer's input come from
+enum Controls {
+    Live {
+        recorder: Option<ReplayRecorder<BufWriter<filesystem::File>>>,
+    },
+    Replay(Replay),
+}
+
+/// Connects the simulation to ggez, chat, the keyboard and the speakers
 pub struct GameState {
-    send_to_chat: Sender<String>,
-    receive_from_chat: Receiver<ChatMessage>,
+    chat_backend: Box<dyn ChatBackend>,
+    controls: Controls,
     screen_size: (f32, f32),
     interface: Interface,
-    game_objects: Vec<GameObject>,
-    player_hit_object_event: Receiver<Chatter>,
-    running_state: RunningState,
+    simulation: Simulation,
+    fixed_timestep: FixedTimestep,
     credits: Option<Credits>,
     splash: Splash,
-    game_start_time: Instant,
+    paused_splash: Splash,
+    /// resume when the window gets focus back, unless the game was paused some other way
+    paused_by_focus_loss: bool,
     object_sound: audio::Source,
-    scores: HashMap<String, u128>,
+    heart_sound: audio::Source,
+    jump_sound: audio::Source,
+    hit_sound: audio::Source,
 }
 
 impl GameState {
     pub fn new(
-        send_to_chat: Sender<String>,
-        receive_from_chat: Receiver<ChatMessage>,
+        chat_backend: Box<dyn ChatBackend>,
+        screen_size: (f32, f32),
+        config: Config,
+        seed: u64,
+        context: &mut Context,
+    ) -> GameResult<GameState> {
+        let replay_file_name = format!("/round_{}.replay", seed);
+        let recorder = match Self::create_recorder(context, &replay_file_name, seed) {
+            Ok(recorder) => Some(recorder),
+            Err(error) => {
+                eprintln!("Error creating replay file {}: {}", replay_file_name, error);
+                None
+            }
+        };
+        let controls = Controls::Live { recorder };
+        Self::create(chat_backend, controls, screen_size, config, seed, context)
+    }
+
+    /// Play back a recorded round instead of listening to chat and the keyboard.
+    /// The round will only play out the same if the config matches the one it was recorded with.
+    /// Nothing is sent to chat while watching, what would have been said is printed instead.
+    pub fn new_replay(
+        replay: Replay,
+        screen_size: (f32, f32),
+        config: Config,
+        context: &mut Context,
+    ) -> GameResult<GameState> {
+        let seed = replay.seed;
+        Self::create(
+            Box::new(ConsoleChatBackend),
+            Controls::Replay(replay),
+            screen_size,
+            config,
+            seed,
+            context,
+        )
+    }
+
+    fn create(
+        chat_backend: Box<dyn ChatBackend>,
+        controls: Controls,
         screen_size: (f32, f32),
+        config: Config,
+        seed: u64,
         context: &mut Context,
     ) -> GameResult<GameState> {
-        let game_started_message = format!("In {} seconds the Get the Streamer game will begin, you can play through chat with the commands on the right side of the game.", SPLASH_DURATION.as_secs());
-        send_to_chat.send(game_started_message).unwrap();
-        let mut interface = Interface::new(context, screen_size, LIVES)?;
+        let sprites = Sprites::new(context, &config.objects)?;
+        let mut interface = Interface::new(
+            context,
+            screen_size,
+            config.round.lives,
+            config.world.drop_zone_count,
+            &config.objects,
+            &sprites,
+        )?;
 
         // create timer block
         let timer_game_object = Self::create_timer(
             screen_size,
             context,
             interface.width,
-            SPLASH_DURATION,
+            config.round.splash_duration(),
             (0.0, 1.0, 0.0),
         )?;
         interface.add_game_object(timer_game_object);
 
-        // create player
-        let player_scale = 4.0;
-        let player_forward_sprite = Sprite::new(context, "/player_forward.png", 8, 1)?;
-        let player_left_sprite = Sprite::new(context, "/player_left.png", 8, 1)?;
-        let player_draw_system =
-            PlayerDrawSystem::new(player_left_sprite, player_forward_sprite, player_scale);
-        let player_size = player_draw_system.get_size().unwrap_or((50.0, 50.0));
-        let (send_player_hit_object_event, receive_player_hit_object_event) = channel();
-        let player_physics_system = PlayerPhysics::new(context, send_player_hit_object_event);
-        let player = GameObject::new(
-            250.0,
-            250.0,
-            Some(Box::new(player_draw_system)),
-            player_size.0,
-            player_size.1,
-            Some(Box::new(player_physics_system)),
-            true,
-            None,
-            GameObjectType::Player,
-            Some(Box::new(PlayerLifeSystem::new())),
+        let arena_size = get_arena_size(screen_size);
+        let fixed_timestep = FixedTimestep::new(
+            config.world.framerate_target,
+            config.world.max_updates_per_frame,
         );
-
-        let game_objects = vec![player];
-        let splash = Splash::new(
-            (screen_size.0 - interface.width, screen_size.1),
-            context,
-            SPLASH_DURATION,
-        );
-        let game_start_time = Instant::now();
+        let mut simulation = Simulation::new(arena_size, Some(sprites), config, seed);
+        if let Some(high_scores) = Self::load_high_scores(context) {
+            simulation.set_high_scores(high_scores.get_scores().clone());
+        }
+        let splash = Splash::new("Starting Soon", arena_size, context);
+        let paused_splash = Splash::new("Paused", arena_size, context);
 
         Ok(GameState {
-            send_to_chat,
-            receive_from_chat,
+            chat_backend,
+            controls,
             screen_size,
             interface,
-            game_objects,
-            player_hit_object_event: receive_player_hit_object_event,
-            running_state: RunningState::StartingSoon,
+            simulation,
+            fixed_timestep,
             credits: None,
             splash,
-            game_start_time,
-            object_sound: audio::Source::new(context, "/threeTone1.ogg").unwrap(),
-            scores: HashMap::new(),
+            paused_splash,
+            paused_by_focus_loss: false,
+            object_sound: audio::Source::new(context, "/threeTone1.ogg")?,
+            heart_sound: audio::Source::new(context, "/phaserUp7.ogg")?,
+            jump_sound: audio::Source::new(context, "/phaseJump1.ogg")?,
+            hit_sound: audio::Source::new(context, "/zap2.ogg")?,
         })
     }
 
-    fn handle_command(
-        &mut self,
-        command: Option<Command>,
+    fn create_recorder(
         context: &mut Context,
-    ) -> GameResult<()> {
-        if let Some(command) = command {
-            let chatter = command.chatter.clone();
-            self.object_sound.play().unwrap();
-            self.game_objects.push(command.handle(
-                self.interface.get_column_coordinates_by_index(command.id),
-                context,
-            )?);
-            let score = self.scores.entry(chatter.name).or_insert(0);
-            *score += 1;
-        }
-        Ok(())
-    }
-
-    fn get_player(&self) -> Option<&GameObject> {
-        self.game_objects
-            .iter()
-            .find(|game_object| game_object.my_type == GameObjectType::Player)
+        file_name: &str,
+        seed: u64,
+    ) -> GameResult<ReplayRecorder<BufWriter<filesystem::File>>> {
+        let file = filesystem::create(context, file_name)?;
+        Ok(ReplayRecorder::new(BufWriter::new(file), seed)?)
     }
 
     fn create_timer(
@@ -158,8 +227,7 @@ impl GameState {
     ) -> GameResult<GameObject> {
         let timer_draw_system = TimerDrawSystem::new(screen_size, context, color)?;
         let timer_size = timer_draw_system.get_size().unwrap_or((5.0, screen_size.1));
-        let timer_physics_system =
-            TimerPhysicsSystem::new(timer_size.1, duration, FRAMERATE_TARGET as f32);
+        let timer_physics_system = TimerPhysicsSystem::new(timer_size.1, duration);
         let timer_game_object = GameObject::new(
             screen_size.0 - interface_width,
             0.0,
@@ -175,199 +243,343 @@ impl GameState {
         Ok(timer_game_object)
     }
 
-    fn update_scores(&self, high_scores: &mut HashMap<String, u128>) {
-        for (username, score) in &self.scores {
-            let high_score = high_scores.entry(username.to_owned()).or_insert(0);
-            *high_score += *score;
+    /// The countdown and who has joined so far, for the splash screen
+    fn get_lobby_details(&self) -> Vec<String> {
+        let mut details = vec![];
+        let seconds_left = self
+            .simulation
+            .get_time_until_round_starts()
+            .unwrap_or_default()
+            .as_secs_f32()
+            .ceil();
+        let players_needed = self.simulation.get_players_needed();
+        if seconds_left > 0.0 {
+            details.push(format!("Starting in {}", seconds_left));
+        } else if players_needed > 0 {
+            details.push(format!(
+                "Waiting for {}",
+                simulation::describe_players(players_needed)
+            ));
         }
-    }
+        details.push("Type #join to play".to_owned());
 
-    fn send_game_started_message(&self) {
-        let message = format!(
-            "You have {} seconds to send your commands to Get the Streamer!",
-            GAME_TIME.as_secs()
-        );
-        if let Err(error) = self.send_to_chat.send(message) {
-            eprintln!("error sending game started message to chat: {}", error);
+        let players = self.simulation.get_lobby_players();
+        let names: Vec<&str> = players
+            .iter()
+            .take(MAX_LOBBY_NAMES)
+            .map(|player| player.name.as_str())
+            .collect();
+        for line in names.chunks(LOBBY_NAMES_PER_LINE) {
+            details.push(line.join(", "));
         }
+        if players.len() > names.len() {
+            details.push(format!("and {} more", players.len() - names.len()));
+        }
+        details
     }
 
-    fn send_game_ended_message(&self, winner: RunningState) {
-        let (highest_scorer, score) = self
-            .get_highest_scorer()
-            .unwrap_or_else(|| ("nobody".to_owned(), 0));
-
-        let message = match winner {
-            RunningState::ChatWon => format!(
-                "You all won, highest scorer was {} with {} points!",
-                highest_scorer, score
-            ),
-            _ => format!(
-                "The Streamer won the game despite the best efforts of {} who got {} points!",
-                highest_scorer, score
-            ),
+    /// Pausing from the keyboard or the window losing focus is recorded so replays pause
+    /// at the same time, replays ignore both since they play back the recorded pauses.
+    /// Chat is only told about pauses from the keyboard, so alt-tabbing doesn't spam it.
+    fn set_paused(&mut self, is_paused: bool, tell_chat: bool) {
+        let recorder = match &mut self.controls {
+            Controls::Live { recorder } => recorder,
+            Controls::Replay(_) => return,
+        };
+        let result = if is_paused {
+            self.simulation.pause()
+        } else {
+            self.simulation.resume()
         };
+        if result.is_err() {
+            return;
+        }
+        self.paused_by_focus_loss = false;
 
-        if let Err(error) = self.send_to_chat.send(message) {
-            eprintln!("error sending game ended message to chat: {}", error);
+        if let Some(recorder) = recorder {
+            if let Err(error) = recorder.record_pause(self.simulation.get_ticks(), is_paused) {
+                eprintln!("Error recording pause to replay: {}", error);
+            }
+        }
+        if !tell_chat {
+            return;
+        }
+        let message = if is_paused {
+            "The streamer paused the game"
+        } else {
+            "The game is back on"
+        };
+        if let Err(error) = self.chat_backend.send(message.to_owned()) {
+            eprintln!("error sending message to chat: {}", error);
         }
     }
 
-    fn end_game(&mut self, new_running_state: RunningState) {
-        self.send_game_ended_message(new_running_state);
-        self.running_state = new_running_state;
+    fn get_player_input(context: &Context) -> PlayerInput {
+        PlayerInput {
+            left: keyboard::is_key_pressed(context, KeyCode::A),
+            right: keyboard::is_key_pressed(context, KeyCode::S)
+                || keyboard::is_key_pressed(context, KeyCode::D),
+            jump: keyboard::is_key_pressed(context, KeyCode::Space),
+        }
     }
 
-    fn get_highest_scorer(&self) -> Option<(String, u128)> {
-        if let Some(scorer) = self.scores.iter().max_by(|a, b| a.1.cmp(b.1)) {
-            Some((scorer.0.to_owned(), *scorer.1))
+    fn handle_event(&mut self, event: GameEvent, context: &mut Context) -> GameResult<()> {
+        match event {
+            GameEvent::SendToChat(message) => {
+                if let Err(error) = self.chat_backend.send(message) {
+                    eprintln!("error sending message to chat: {}", error);
+                }
+            }
+            GameEvent::ObjectDropped(_) => self.object_sound.play()?,
+            GameEvent::PlayerJumped => self.jump_sound.play()?,
+            GameEvent::PlayerHealed => self.heart_sound.play()?,
+            GameEvent::PlayerHit(_) => self.hit_sound.play()?,
+            GameEvent::RunningStateChanged(RunningState::Playing) => {
+                println!("Round started with seed {}", self.simulation.get_seed());
+                let config = self.simulation.get_config();
+                let timer = Self::create_timer(
+                    self.screen_size,
+                    context,
+                    self.interface.width,
+                    config.round.game_time(),
+                    (1.0, 0.0, 0.0),
+                )?;
+                self.interface.add_game_object(timer);
+            }
+            GameEvent::RunningStateChanged(RunningState::StartingSoon) => {
+                // the round was restarted, the game starts out in this state without an event
+                self.credits = None;
+                let config = self.simulation.get_config();
+                let timer = Self::create_timer(
+                    self.screen_size,
+                    context,
+                    self.interface.width,
+                    config.round.splash_duration(),
+                    (0.0, 1.0, 0.0),
+                )?;
+                self.interface.clear_game_objects();
+                self.interface.add_game_object(timer);
+                if let Some(high_scores) = Self::load_high_scores(context) {
+                    self.simulation
+                        .set_high_scores(high_scores.get_scores().clone());
+                }
+            }
+            GameEvent::RunningStateChanged(RunningState::Paused)
+            | GameEvent::RunningStateResumed(_) => {}
+            GameEvent::RunningStateChanged(RunningState::PlayerWon)
+            | GameEvent::RunningStateChanged(RunningState::ChatWon) => {
+                let metrics = self.simulation.get_command_queue_metrics();
+                println!(
+                    "Drop queue: {} landed, at most {} waiting and {} ticks waited, {} merged, {} thrown out when full, {} too late",
+                    metrics.spawned,
+                    metrics.max_depth,
+                    metrics.max_wait_ticks,
+                    metrics.merged,
+                    metrics.dropped,
+                    metrics.expired
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn load_high_scores(context: &Context) -> Option<HighScores> {
+        match HighScores::load(filesystem::user_config_dir(context)) {
+            Ok(high_scores) => {
+                for warning in high_scores.get_warnings() {
+                    eprintln!("Warning loading high scores: {}", warning);
+                }
+                Some(high_scores)
+            }
+            Err(error) => {
+                eprintln!(
+                    "Error loading high scores, they won't be saved until fixed: {}",
+                    error
+                );
+                None
+            }
+        }
+    }
+
+    /// Everything in the arena while a round is on, with the drops that are about to land
+    fn draw_arena(&self, context: &mut Context, alpha: f32) -> GameResult<()> {
+        for game_object in self.simulation.get_game_objects() {
+            game_object.draw(context, alpha)?;
+        }
+        let sprites = self.simulation.get_sprites();
+        self.interface.draw_incoming_drops(
+            context,
+            self.simulation.get_incoming_drops(),
+            sprites,
+            self.simulation.get_clock(),
+        )?;
+        self.interface.draw_drop_panel(
+            context,
+            &self.simulation.get_upcoming_drops(),
+            self.simulation.get_recent_hits(),
+            sprites,
+        )
+    }
+
+    /// Add this round's scores to the high scores saved on disk, returning the new totals
+    fn update_high_scores(
+        &self,
+        context: &Context,
+        scores: &HashMap<String, Score>,
+    ) -> HashMap<String, Score> {
+        let mut high_scores = match Self::load_high_scores(context) {
+            Some(high_scores) => high_scores,
+            None => return HashMap::new(),
+        };
+        high_scores.add_scores(scores);
+        if let Err(error) = high_scores.save() {
+            eprintln!("Error saving high scores to disk: {}", error);
+        }
+        high_scores.get_scores().clone()
+    }
+
+    fn update_credits(&mut self, context: &mut Context) -> GameResult<()> {
+        if let Some(credits) = &mut self.credits {
+            // the simulation starts the next round on its own after the break
+            if !credits.update() && self.simulation.is_session_over() {
+                ggez::event::quit(context);
+            }
         } else {
-            None
+            let scores = self.simulation.get_scores().clone();
+            let high_scores = self.update_high_scores(context, &scores);
+            let running_state = self.simulation.get_running_state();
+            self.credits = Some(Credits::new(
+                running_state,
+                context,
+                self.screen_size,
+                &high_scores,
+                &scores,
+                self.simulation.get_rng(),
+            )?);
         }
+        Ok(())
     }
 }
+
 impl EventHandler for GameState {
     fn update(&mut self, context: &mut Context) -> GameResult {
-        if let Ok(chat_message) = self.receive_from_chat.try_recv() {
-            if matches!(self.running_state, RunningState::Playing) {
-                let chatter_name = if let Some(display_name) = chat_message.display_name {
-                    display_name
-                } else {
-                    chat_message.name.clone()
-                };
-                match Command::new(
-                    &chat_message.message,
-                    Chatter::new(
-                        chatter_name,
-                        chat_message.color_rgb,
-                        chat_message.subscriber,
-                    ),
-                ) {
-                    Err(error) => self.send_to_chat.send(error.to_owned()).unwrap(),
-                    Ok(command) => self.handle_command(command, context)?,
+        if let Controls::Live { recorder } = &mut self.controls {
+            while let Some(chat_message) = self.chat_backend.receive() {
+                if let Some(recorder) = recorder {
+                    if let Err(error) = recorder.record_chat_message(
+                        self.simulation.get_ticks(),
+                        &chat_message.chatter,
+                        &chat_message.message,
+                    ) {
+                        eprintln!("Error recording chat message to replay: {}", error);
+                    }
                 }
+                self.simulation
+                    .handle_chat_message(&chat_message.message, chat_message.chatter);
             }
         }
 
-        while timer::check_update_time(context, FRAMERATE_TARGET) {
-            match self.running_state {
-                RunningState::StartingSoon => {
-                    if let Err(error) = self.interface.update(context, LIVES) {
-                        eprintln!("Error updating game objects in interface: {}", error);
-                    }
-                    if self.splash.is_done() {
-                        self.send_game_started_message();
-                        self.running_state = RunningState::Playing;
-                        let timer = Self::create_timer(
-                            self.screen_size,
-                            context,
-                            self.interface.width,
-                            GAME_TIME,
-                            (1.0, 0.0, 0.0),
-                        )?;
-                        self.interface.add_game_object(timer);
-                        self.game_start_time = Instant::now();
+        self.fixed_timestep.add_frame_time(timer::delta(context));
+        while self.fixed_timestep.take_step() {
+            let tick = self.simulation.get_ticks();
+            let input = match &mut self.controls {
+                Controls::Live { recorder, .. } => {
+                    let input = Self::get_player_input(context);
+                    if let Some(recorder) = recorder {
+                        if let Err(error) = recorder.record_input(tick, &input) {
+                            eprintln!("Error recording input to replay: {}", error);
+                        }
                     }
+                    input
                 }
-                RunningState::Playing => {
-                    // get the player lives left
-                    let lives_left = if let Some(player) = self.get_player() {
-                        player.get_lives_left().unwrap_or(3)
-                    } else {
-                        0
-                    };
-
-                    if let Err(error) = self.interface.update(context, lives_left) {
-                        eprintln!("Error updating game objects in interface: {}", error);
+                Controls::Replay(replay) => {
+                    for is_paused in replay.take_pauses(tick) {
+                        let result = if is_paused {
+                            self.simulation.pause()
+                        } else {
+                            self.simulation.resume()
+                        };
+                        if let Err(error) = result {
+                            eprintln!("Error replaying pause: {}", error);
+                        }
                     }
-
-                    let game_time_left =
-                        GAME_TIME.as_secs() - self.game_start_time.elapsed().as_secs();
-                    if game_time_left == 0 {
-                        self.end_game(RunningState::PlayerWon);
+                    for (chatter, message) in replay.take_chat_messages(tick) {
+                        self.simulation.handle_chat_message(&message, chatter);
                     }
+                    replay.take_input(tick)
+                }
+            };
+            for event in self.simulation.update(&input) {
+                self.handle_event(event, context)?;
+            }
 
-                    let arena_size = (
-                        self.screen_size.0 - self.interface.width,
-                        self.screen_size.1,
-                    );
-
-                    let collidable_game_objects: Vec<GameObject> = self
-                        .game_objects
-                        .clone()
-                        .into_iter()
-                        .filter(|game_object| game_object.collidable)
-                        .collect();
-
-                    self.game_objects.iter_mut().for_each(|game_object| {
-                        if let Err(error) = game_object.update(
-                            timer::time_since_start(context),
-                            arena_size,
-                            context,
-                            &collidable_game_objects,
-                        ) {
-                            eprintln!("error running update: {}", error)
-                        }
-                    });
+            if let Err(error) = self.interface.update(
+                self.simulation.get_clock(),
+                self.simulation.get_player_lives_left(),
+            ) {
+                eprintln!("Error updating game objects in interface: {}", error);
+            }
 
-                    self.game_objects
-                        .retain(|game_object| game_object.is_alive());
+            if self.simulation.get_running_state().is_game_over() {
+                self.update_credits(context)?;
+            }
+        }
+        Ok(())
+    }
 
-                    if let Ok(chatter) = self.player_hit_object_event.try_recv() {
-                        let message_to_chat = format!("Hit! {} gets 10 points", &chatter.name);
-                        self.send_to_chat.send(message_to_chat).unwrap();
-                        let score = self.scores.entry(chatter.name).or_insert(0);
-                        *score += 10;
-                    }
+    fn key_down_event(
+        &mut self,
+        context: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        repeat: bool,
+    ) {
+        match keycode {
+            // ggez quits on escape unless the game handles its own keys
+            KeyCode::Escape => ggez::event::quit(context),
+            KeyCode::P if !repeat => {
+                let is_paused = self.simulation.get_running_state() == RunningState::Paused;
+                self.set_paused(!is_paused, true);
+            }
+            _ => {}
+        }
+    }
 
-                    if self
-                        .game_objects
-                        .iter()
-                        .find(|game_object| game_object.my_type == GameObjectType::Player)
-                        .is_none()
-                    {
-                        self.end_game(RunningState::ChatWon);
-                    }
-                }
-                RunningState::ChatWon | RunningState::PlayerWon => {
-                    if let Some(credits) = &mut self.credits {
-                        if !credits.update() {
-                            ggez::event::quit(context);
-                        }
-                    } else {
-                        let mut high_scores = utilities::load_scores(SCORES_FILE_NAME, context);
-                        self.update_scores(&mut high_scores);
-                        if let Err(error) =
-                            utilities::save_scores(context, SCORES_FILE_NAME, &high_scores)
-                        {
-                            eprintln!("Error saving high scores to disk: {}", error);
-                        }
-                        self.credits = Some(Credits::new(
-                            self.running_state,
-                            context,
-                            self.screen_size,
-                            &high_scores,
-                            &self.scores,
-                        )?);
-                    }
-                }
+    fn focus_event(&mut self, _context: &mut Context, gained: bool) {
+        if gained {
+            if self.paused_by_focus_loss {
+                self.set_paused(false, false);
             }
+        } else if self.simulation.get_running_state() != RunningState::Paused {
+            self.set_paused(true, false);
+            self.paused_by_focus_loss = self.simulation.get_running_state() == RunningState::Paused;
         }
-        Ok(())
     }
 
     fn draw(&mut self, context: &mut Context) -> GameResult {
         graphics::clear(context, BLACK);
 
+        let running_state = self.simulation.get_running_state();
+        // nothing moves while paused, so objects stay where the last update left them
+        let alpha = if running_state == RunningState::Paused {
+            1.0
+        } else {
+            self.fixed_timestep.get_alpha()
+        };
         self.interface
-            .draw(context, self.screen_size, &self.running_state)?;
+            .draw(context, self.screen_size, &running_state, alpha)?;
 
-        match self.running_state {
-            RunningState::StartingSoon => self.splash.draw(context)?,
+        match running_state {
+            RunningState::StartingSoon => {
+                let details = self.get_lobby_details();
+                self.splash.draw(context, &details)?
+            }
             RunningState::Playing => {
-                for game_object in self.game_objects.iter() {
-                    game_object.draw(context)?;
-                }
+                self.draw_arena(context, alpha)?;
+            }
+            RunningState::Paused => {
+                self.draw_arena(context, alpha)?;
+                self.paused_splash.draw(context, &[])?;
             }
             RunningState::PlayerWon | RunningState::ChatWon => {
                 if let Some(credits) = &self.credits {
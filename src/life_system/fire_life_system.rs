@@ -1,34 +1,38 @@
-use std::time::{Duration, Instant};
-
+use crate::game_clock::GameClock;
 use ggez::graphics::Rect;
+use std::time::Duration;
 
 use super::LifeSystem;
 
 #[derive(Debug)]
 pub struct FireLifeSystem {
-    birth_time: Instant,
-    time_to_live: Duration,
+    /// in game time
+    time_left: Duration,
 }
 
 impl FireLifeSystem {
-    pub fn new() -> FireLifeSystem {
+    pub fn new(lifetime: Duration) -> FireLifeSystem {
         FireLifeSystem {
-            birth_time: Instant::now(),
-            time_to_live: Duration::from_secs(6),
+            time_left: lifetime,
         }
     }
 }
 
 impl LifeSystem for FireLifeSystem {
     fn is_alive(&self) -> bool {
-        self.birth_time.elapsed() < self.time_to_live
+        self.time_left > Duration::default()
     }
 
     fn hit(&mut self) -> bool {
         false
     }
 
-    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {}
+    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect, clock: &GameClock) {
+        self.time_left = self
+            .time_left
+            .checked_sub(clock.get_delta())
+            .unwrap_or_default();
+    }
 
     fn gain_life(&mut self) {}
 
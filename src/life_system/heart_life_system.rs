@@ -1,3 +1,4 @@
+use crate::game_clock::GameClock;
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
@@ -27,7 +28,7 @@ impl LifeSystem for HeartLifeSystem {
         true
     }
 
-    fn update(&mut self, screen_size: (f32, f32), location: &Rect) {
+    fn update(&mut self, screen_size: (f32, f32), location: &Rect, _clock: &GameClock) {
         if location.y > screen_size.1 {
             self.alive = false;
         }
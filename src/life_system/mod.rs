@@ -4,6 +4,7 @@ mod player_life_system;
 mod snake_life_system;
 mod sword_life_system;
 
+use crate::game_clock::GameClock;
 pub use fire_life_system::FireLifeSystem;
 use ggez::graphics::Rect;
 pub use heart_life_system::HeartLifeSystem;
@@ -17,7 +18,11 @@ where
 {
     fn is_alive(&self) -> bool;
     fn hit(&mut self) -> bool;
-    fn update(&mut self, screen_size: (f32, f32), location: &Rect);
+    /// Like `hit`, for things that can lose more than one life at once
+    fn hit_for(&mut self, _damage: u8) -> bool {
+        self.hit()
+    }
+    fn update(&mut self, screen_size: (f32, f32), location: &Rect, clock: &GameClock);
     fn gain_life(&mut self);
     fn get_lives_left(&self) -> u8;
 }
@@ -1,44 +1,56 @@
 use ggez::graphics::Rect;
+use std::time::Duration;
 
-use super::LifeSystem;
+use crate::{config::PlayerConfig, game_clock::GameClock};
 
-const IFRAMES_REDUCE_EVERY: u32 = 720;
-const IFRAMES_REDUCE_BY: u8 = 10;
-const IFRAMES_MIN: u8 = 10;
-const IFRAMES_START: u8 = 120;
+use super::LifeSystem;
 
+/// The streamer's lives, and how long they can't be hit after getting hit. That time
+/// shrinks as the round goes on. Everything is counted in game time.
 #[derive(Debug)]
 pub struct PlayerLifeSystem {
     lives: u8,
-    iframes_left: u8,
-    iframes: u8,
-    frame_count: u32,
+    iframes_left: Duration,
+    iframes: Duration,
+    time_until_reduce: Duration,
+    iframes_min: Duration,
+    iframes_reduce_by: Duration,
+    iframes_reduce_every: Duration,
 }
 
 impl PlayerLifeSystem {
-    pub fn new() -> PlayerLifeSystem {
+    pub fn new(lives: u8, config: &PlayerConfig) -> PlayerLifeSystem {
+        let iframes_reduce_every = Duration::from_secs_f32(config.iframes_reduce_every_seconds);
         PlayerLifeSystem {
-            lives: 3,
-            iframes_left: 0,
-            iframes: IFRAMES_START,
-            frame_count: IFRAMES_REDUCE_EVERY,
+            lives,
+            iframes_left: Duration::default(),
+            iframes: Duration::from_secs_f32(config.iframes_start_seconds),
+            time_until_reduce: iframes_reduce_every,
+            iframes_min: Duration::from_secs_f32(config.iframes_min_seconds),
+            iframes_reduce_by: Duration::from_secs_f32(config.iframes_reduce_by_seconds),
+            iframes_reduce_every,
         }
     }
 
-    fn update_iframes(&mut self) {
-        if self.iframes > IFRAMES_MIN {
-            self.frame_count -= 1;
-            if self.frame_count == 0 {
-                self.frame_count = IFRAMES_REDUCE_EVERY;
-                self.iframes -= IFRAMES_REDUCE_BY;
+    fn update_iframes(&mut self, delta: Duration) {
+        if self.iframes > self.iframes_min {
+            self.time_until_reduce = self
+                .time_until_reduce
+                .checked_sub(delta)
+                .unwrap_or_default();
+            if self.time_until_reduce == Duration::default() {
+                self.time_until_reduce = self.iframes_reduce_every;
+                self.iframes = self
+                    .iframes
+                    .checked_sub(self.iframes_reduce_by)
+                    .unwrap_or_default()
+                    .max(self.iframes_min);
             }
         }
     }
 
-    fn update_iframes_left(&mut self) {
-        if self.iframes_left > 0 {
-            self.iframes_left -= 1;
-        }
+    fn update_iframes_left(&mut self, delta: Duration) {
+        self.iframes_left = self.iframes_left.checked_sub(delta).unwrap_or_default();
     }
 }
 
@@ -48,8 +60,12 @@ impl LifeSystem for PlayerLifeSystem {
     }
 
     fn hit(&mut self) -> bool {
-        if self.iframes_left == 0 {
-            self.lives -= 1;
+        self.hit_for(1)
+    }
+
+    fn hit_for(&mut self, damage: u8) -> bool {
+        if self.iframes_left == Duration::default() {
+            self.lives = self.lives.saturating_sub(damage);
             self.iframes_left = self.iframes;
             true
         } else {
@@ -57,9 +73,10 @@ impl LifeSystem for PlayerLifeSystem {
         }
     }
 
-    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {
-        self.update_iframes();
-        self.update_iframes_left();
+    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect, clock: &GameClock) {
+        let delta = clock.get_delta();
+        self.update_iframes(delta);
+        self.update_iframes_left(delta);
     }
 
     fn gain_life(&mut self) {
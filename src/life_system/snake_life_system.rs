@@ -1,3 +1,4 @@
+use crate::game_clock::GameClock;
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
@@ -22,7 +23,7 @@ impl LifeSystem for SnakeLifeSystem {
         true
     }
 
-    fn update(&mut self, screen_size: (f32, f32), location: &Rect) {
+    fn update(&mut self, screen_size: (f32, f32), location: &Rect, _clock: &GameClock) {
         if location.x + location.w < 0.0 || location.x > screen_size.0 {
             self.on_screen = false;
         }
@@ -1,3 +1,4 @@
+use crate::game_clock::GameClock;
 use ggez::graphics::Rect;
 
 use super::LifeSystem;
@@ -9,10 +10,10 @@ pub struct SwordLifeSystem {
 }
 
 impl SwordLifeSystem {
-    pub fn new() -> SwordLifeSystem {
+    pub fn new(breaks_at: u8) -> SwordLifeSystem {
         SwordLifeSystem {
             bounces: 0,
-            breaks_at: 7,
+            breaks_at,
         }
     }
 }
@@ -27,7 +28,7 @@ impl LifeSystem for SwordLifeSystem {
         true
     }
 
-    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect) {}
+    fn update(&mut self, _screen_size: (f32, f32), _location: &Rect, _clock: &GameClock) {}
 
     fn gain_life(&mut self) {}
 
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use crate::{chatter::Chatter, command::Command};
+
+const JOIN_COMMAND: &str = "#join";
+
+/// Chatters getting ready during the splash screen, either by typing `#join` or by sending
+/// the drop they want to start the round with
+#[derive(Default)]
+pub struct Lobby {
+    /// in the order they joined
+    players: Vec<Chatter>,
+    /// the drops from each player's first message, they land as soon as the round starts
+    first_drops: Vec<Vec<Command>>,
+    /// user ids of the players that were told only their first message of drops counts
+    told: HashSet<String>,
+    /// chat has been told the round is waiting for more players
+    is_waiting: bool,
+}
+
+impl Lobby {
+    pub fn is_join_message(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case(JOIN_COMMAND)
+    }
+
+    pub fn join(&mut self, chatter: Chatter) {
+        match self
+            .players
+            .iter_mut()
+            .find(|player| player.user_id == chatter.user_id)
+        {
+            Some(player) => *player = chatter,
+            None => self.players.push(chatter),
+        }
+    }
+
+    /// Joins the chatter too. Only the drops from the first message each chatter sends are
+    /// kept, what to tell the chatter is returned the first time a later message is left out.
+    pub fn queue_first_drops(
+        &mut self,
+        chatter: Chatter,
+        commands: Vec<Command>,
+    ) -> Option<String> {
+        let user_id = chatter.user_id.clone();
+        let name = chatter.name.clone();
+        self.join(chatter);
+        let has_dropped = self
+            .first_drops
+            .iter()
+            .flatten()
+            .any(|first_drop| first_drop.chatter.user_id == user_id);
+        if !has_dropped {
+            self.first_drops.push(commands);
+            None
+        } else if self.told.insert(user_id) {
+            Some(format!(
+                "Only {}'s first message of drops counts before the round starts",
+                name
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_players(&self) -> &[Chatter] {
+        &self.players
+    }
+
+    /// Returns true the first time it is called since the lobby was cleared
+    pub fn start_waiting(&mut self) -> bool {
+        !std::mem::replace(&mut self.is_waiting, true)
+    }
+
+    /// Empty the lobby for the next round, handing back the drops that were sent early
+    pub fn take_first_drops(&mut self) -> Vec<Vec<Command>> {
+        self.players.clear();
+        self.told.clear();
+        self.is_waiting = false;
+        std::mem::take(&mut self.first_drops)
+    }
+}
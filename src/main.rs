@@ -1,19 +1,23 @@
-use get_the_streamer_game::GameState;
+use get_the_streamer_game::{
+    Config, GameState, ObjectRegistry, Replay, TwitchChatBackend, TwitchSettings, WINDOW_SIZE,
+};
 use ggez::conf::{WindowMode, WindowSetup};
 use ggez::{event, ContextBuilder};
-use std::sync::mpsc::channel;
-use std::thread;
-use twitch_chat_wrapper::chat_message::ChatMessage;
-
-const WINDOW_SIZE: (f32, f32) = (1920.0, 1080.0);
+use std::fs::File;
+use std::io::BufReader;
 
 fn main() {
-    let (send_to_game, receive_from_twitch) = channel::<ChatMessage>();
-    let (send_to_twitch, receive_from_game) = channel::<String>();
-
-    let _twitchchat_thread = thread::spawn(move || {
-        twitch_chat_wrapper::run(receive_from_game, send_to_game).unwrap();
-    });
+    let config = match Config::load(get_argument("--config").as_deref()).and_then(|mut config| {
+        config.objects = ObjectRegistry::load(get_argument("--objects").as_deref())?;
+        Ok(config)
+    }) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let replay = get_argument("--replay").map(|replay_path| load_replay(&replay_path));
 
     let (context, event_loop) = &mut match ContextBuilder::new("Get the Streamer", "Brooks Builds")
         .window_setup(WindowSetup::default().title("Get the Streamer"))
@@ -24,10 +28,70 @@ fn main() {
         Err(error) => panic!(error),
     };
 
-    let game_state =
-        &mut GameState::new(send_to_twitch, receive_from_twitch, WINDOW_SIZE, context).unwrap();
+    let game_state = &mut if let Some(replay) = replay {
+        GameState::new_replay(replay, WINDOW_SIZE, config, context)
+    } else {
+        let seed = get_seed().or(config.seed).unwrap_or_else(rand::random);
+        let twitch_chat_backend = match TwitchSettings::from_env()
+            .and_then(|settings| TwitchChatBackend::connect(&settings))
+        {
+            Ok(twitch_chat_backend) => twitch_chat_backend,
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        };
+        GameState::new(
+            Box::new(twitch_chat_backend),
+            WINDOW_SIZE,
+            config,
+            seed,
+            context,
+        )
+    }
+    .unwrap();
     match event::run(context, event_loop, game_state) {
         Ok(_) => println!("Thanks for playing!"),
         Err(error) => eprintln!("Error occurred: {}", error),
     };
 }
+
+/// Get the value that follows a command line flag, for example `--seed 1234`
+fn get_argument(flag: &str) -> Option<String> {
+    let mut arguments = std::env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        if argument == flag {
+            return arguments.next();
+        }
+    }
+    None
+}
+
+/// The seed passed in with `--seed <number>` so a round can be replayed
+fn get_seed() -> Option<u64> {
+    match get_argument("--seed").map(|seed| seed.parse()) {
+        Some(Ok(seed)) => Some(seed),
+        Some(Err(_)) => {
+            eprintln!("--seed needs to be followed by a positive whole number");
+            std::process::exit(1);
+        }
+        None => None,
+    }
+}
+
+fn load_replay(replay_path: &str) -> Replay {
+    let replay_file = match File::open(replay_path) {
+        Ok(replay_file) => replay_file,
+        Err(error) => {
+            eprintln!("could not open replay {}: {}", replay_path, error);
+            std::process::exit(1);
+        }
+    };
+    match Replay::load(BufReader::new(replay_file)) {
+        Ok(replay) => replay,
+        Err(error) => {
+            eprintln!("could not load replay {}: {}", replay_path, error);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::HashSet;
+use std::io::Read;
+use std::time::Duration;
+
+use eyre::{eyre, Result};
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::utilities;
+
+const OBJECTS_FILE_NAME: &str = "objects.toml";
+/// used when there is no objects file in the resources folder
+const DEFAULT_OBJECTS: &str = include_str!("../assets/objects.toml");
+/// these always drop a random object, so no object can use them
+const RANDOM_COMMANDS: [&str; 3] = ["#random", "#rand", "#rng"];
+const RANDOM_DESCRIPTION: &str = "drops something random";
+
+/// Everything chat can drop into the arena, loaded from `objects.toml` so new objects
+/// can be added without touching the game's code
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectRegistry {
+    objects: Vec<ObjectDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectDefinition {
+    pub name: String,
+    /// what chat types to drop the object, like `#fire`
+    pub commands: Vec<String>,
+    /// path of the sprite sheet in the resources folder
+    pub sprite: String,
+    /// a few words about what the object does, shown next to its commands in the sidebar
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_one")]
+    pub sprite_columns: u16,
+    #[serde(default = "default_one")]
+    pub sprite_rows: u16,
+    /// the size of the object in the arena, which is also what the streamer collides with
+    pub width: f32,
+    pub height: f32,
+    pub behavior: ObjectBehavior,
+    /// how long the object stays in the arena, only `fire` objects use it
+    pub lifetime_seconds: Option<f32>,
+    /// how many times the object bounces before falling through the floor, only `sword` objects use it
+    pub bounces: Option<u8>,
+    /// how many hearts the streamer loses when hit by the object
+    #[serde(default = "default_one")]
+    pub damage: u8,
+    /// how likely `#random` is to drop this object compared to the others, 0 never drops it
+    #[serde(default = "default_one")]
+    pub random_weight: u32,
+}
+
+/// How an object moves and when it leaves the arena
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectBehavior {
+    /// falls and burns on the floor until its lifetime runs out
+    Fire,
+    /// falls and bounces across the arena, breaking after its last bounce
+    Sword,
+    /// falls and then slithers towards the streamer until it leaves the arena
+    Snake,
+    /// drifts down slowly and gives the streamer a heart back when caught
+    Heart,
+}
+
+/// A command chat can use, as listed in the sidebar
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandHelp<'a> {
+    pub command: &'a str,
+    /// the other commands that do the same thing
+    pub aliases: Vec<&'a str>,
+    pub description: &'a str,
+    /// the object the command drops, none for the commands that drop a random one
+    pub object: Option<&'a ObjectDefinition>,
+    pub helps_streamer: bool,
+}
+
+/// How many letters have to be added, removed, changed or swapped with their neighbor
+/// to turn one word into the other
+fn get_edit_distance(word: &str, other_word: &str) -> usize {
+    let word: Vec<char> = word.chars().collect();
+    let other_word: Vec<char> = other_word.chars().collect();
+    let mut distances = vec![vec![0; other_word.len() + 1]; word.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=word.len() {
+        for j in 1..=other_word.len() {
+            let change = if word[i - 1] == other_word[j - 1] {
+                0
+            } else {
+                1
+            };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + change);
+            if i > 1
+                && j > 1
+                && word[i - 1] == other_word[j - 2]
+                && word[i - 2] == other_word[j - 1]
+            {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[word.len()][other_word.len()]
+}
+
+fn default_one<T: From<u8>>() -> T {
+    T::from(1)
+}
+
+impl ObjectRegistry {
+    /// Load the objects from the given path, or from `objects.toml` in the resources folder
+    /// when no path is given. The objects the game comes with are used if there is no file.
+    pub fn load(path: Option<&str>) -> Result<ObjectRegistry> {
+        let mut objects_file = match path {
+            Some(path) => std::fs::File::open(path)
+                .map_err(|error| eyre!("could not open objects file {}: {}", path, error))?,
+            None => match utilities::get_resource(OBJECTS_FILE_NAME) {
+                Some(objects_file) => objects_file,
+                None => return Ok(ObjectRegistry::default()),
+            },
+        };
+        let mut objects = String::new();
+        objects_file.read_to_string(&mut objects)?;
+        Self::from_toml(&objects)
+    }
+
+    pub fn from_toml(objects: &str) -> Result<ObjectRegistry> {
+        let registry: ObjectRegistry =
+            toml::from_str(objects).map_err(|error| eyre!("invalid objects: {}", error))?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    pub fn get_objects(&self) -> &[ObjectDefinition] {
+        &self.objects
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ObjectDefinition> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    /// The object dropped by a command like `#fire`
+    pub fn find_by_command(&self, command: &str) -> Option<&ObjectDefinition> {
+        self.objects.iter().find(|object| {
+            object
+                .commands
+                .iter()
+                .any(|object_command| object_command.eq_ignore_ascii_case(command))
+        })
+    }
+
+    pub fn is_random_command(command: &str) -> bool {
+        RANDOM_COMMANDS
+            .iter()
+            .any(|random_command| random_command.eq_ignore_ascii_case(command))
+    }
+
+    /// The command a chatter most likely meant to type, for commands that are a letter or two off
+    pub fn suggest_command(&self, command: &str) -> Option<&str> {
+        let command = command.to_lowercase();
+        // a letter off for short commands, more for longer ones so `#gg` isn't mistaken for `#rng`
+        let max_distance = (command.chars().count() / 3).max(1);
+        self.objects
+            .iter()
+            .flat_map(|object| object.commands.iter().map(String::as_str))
+            .chain(RANDOM_COMMANDS.iter().copied())
+            .map(|known_command| {
+                let distance = get_edit_distance(&command, &known_command.to_lowercase());
+                (distance, known_command)
+            })
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, known_command)| known_command)
+    }
+
+    /// Every command chat can use with the commands that do the same thing grouped together,
+    /// the ones that help the streamer last
+    pub fn get_command_help(&self) -> Vec<CommandHelp<'_>> {
+        let attacks = self
+            .objects
+            .iter()
+            .filter(|object| !object.helps_streamer());
+        let helps = self.objects.iter().filter(|object| object.helps_streamer());
+        let random = CommandHelp {
+            command: RANDOM_COMMANDS[0],
+            aliases: RANDOM_COMMANDS[1..].to_vec(),
+            description: RANDOM_DESCRIPTION,
+            object: None,
+            helps_streamer: false,
+        };
+        attacks
+            .map(ObjectDefinition::get_help)
+            .chain(std::iter::once(random))
+            .chain(helps.map(ObjectDefinition::get_help))
+            .collect()
+    }
+
+    /// Pick an object using each object's random weight
+    pub fn choose_random(&self, rng: &mut impl Rng) -> &ObjectDefinition {
+        let total_weight: u32 = self.objects.iter().map(|object| object.random_weight).sum();
+        let mut random = rng.gen::<f32>() * total_weight as f32;
+        for object in &self.objects {
+            if random < object.random_weight as f32 {
+                return object;
+            }
+            random -= object.random_weight as f32;
+        }
+        // rounding can leave a sliver past the last object
+        self.objects
+            .iter()
+            .rev()
+            .find(|object| object.random_weight > 0)
+            .unwrap_or(&self.objects[0])
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.objects.is_empty() {
+            return Err(eyre!(
+                "invalid objects: there needs to be at least one object"
+            ));
+        }
+        if self.objects.iter().all(|object| object.random_weight == 0) {
+            return Err(eyre!(
+                "invalid objects: at least one object needs a `random_weight` above 0"
+            ));
+        }
+
+        let mut names = HashSet::new();
+        let mut commands = HashSet::new();
+        for object in &self.objects {
+            if !names.insert(object.name.as_str()) {
+                return Err(eyre!(
+                    "invalid objects: there is more than one `{}`",
+                    object.name
+                ));
+            }
+            object.validate()?;
+            for command in &object.commands {
+                if !commands.insert(command.to_lowercase()) {
+                    return Err(eyre!(
+                        "invalid objects: more than one object uses `{}`",
+                        command
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ObjectRegistry {
+    fn default() -> Self {
+        Self::from_toml(DEFAULT_OBJECTS).expect("the objects the game comes with are valid")
+    }
+}
+
+impl ObjectDefinition {
+    pub fn lifetime(&self) -> Option<Duration> {
+        self.lifetime_seconds.map(Duration::from_secs_f32)
+    }
+
+    /// Whether chat drops it to help the streamer rather than to hit them
+    pub fn helps_streamer(&self) -> bool {
+        self.behavior == ObjectBehavior::Heart
+    }
+
+    fn get_help(&self) -> CommandHelp<'_> {
+        CommandHelp {
+            command: &self.commands[0],
+            aliases: self.commands[1..].iter().map(String::as_str).collect(),
+            description: &self.description,
+            object: Some(self),
+            helps_streamer: self.helps_streamer(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let ensure = |valid: bool, problem: &str| {
+            if valid {
+                Ok(())
+            } else {
+                Err(eyre!("invalid objects: `{}` {}", self.name, problem))
+            }
+        };
+        ensure(!self.commands.is_empty(), "needs at least one command")?;
+        for command in &self.commands {
+            ensure(
+                command.len() > 1
+                    && command.starts_with('#')
+                    && !command.contains(char::is_whitespace),
+                "has a command that isn't a # followed by a word",
+            )?;
+            ensure(
+                !ObjectRegistry::is_random_command(command),
+                "can't use a command that drops a random object",
+            )?;
+        }
+        ensure(
+            self.sprite_columns > 0 && self.sprite_rows > 0,
+            "needs at least one sprite column and row",
+        )?;
+        ensure(
+            self.width > 0.0 && self.height > 0.0,
+            "needs a `width` and `height` above 0",
+        )?;
+        ensure(
+            self.helps_streamer() || self.damage > 0,
+            "needs `damage` above 0 to hurt the streamer",
+        )?;
+        match self.behavior {
+            ObjectBehavior::Fire => ensure(
+                matches!(self.lifetime_seconds, Some(lifetime) if lifetime > 0.0 && lifetime.is_finite()),
+                "needs a `lifetime_seconds` above 0 that isn't infinite",
+            ),
+            ObjectBehavior::Sword => ensure(
+                matches!(self.bounces, Some(bounces) if bounces > 0),
+                "needs `bounces` above 0",
+            ),
+            ObjectBehavior::Snake | ObjectBehavior::Heart => Ok(()),
+        }
+    }
+}
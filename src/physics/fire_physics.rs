@@ -1,10 +1,9 @@
-use crate::life_system::LifeSystem;
+use crate::{game_clock::GameClock, life_system::LifeSystem, player_input::PlayerInput};
 
 use super::{GameObject, PhysicsSystem};
 use eyre::Result;
 use ggez::graphics::Rect;
 use ggez::nalgebra::Point2;
-use ggez::Context;
 
 #[derive(Debug)]
 pub struct FirePhysics {
@@ -27,17 +26,19 @@ impl PhysicsSystem for FirePhysics {
         location: &mut Rect,
         screen_height: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
+        _input: &PlayerInput,
         _collidable_game_objects: &[GameObject],
         _rotation: &mut f32,
         _life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()> {
+        let delta = clock.get_delta().as_secs_f32();
         if self.affected_by_gravity {
-            self.velocity.y += gravity_force;
+            self.velocity.y += gravity_force * delta;
         }
 
-        location.x += self.velocity.x;
-        location.y += self.velocity.y;
+        location.x += self.velocity.x * delta;
+        location.y += self.velocity.y * delta;
 
         if location.y + location.h >= screen_height.1 {
             self.velocity.y = 0.0;
@@ -1,8 +1,11 @@
-use crate::{game_object::GameObject, game_object_type::GameObjectType, life_system::LifeSystem};
+use crate::{
+    game_clock::GameClock, game_object::GameObject, game_object_type::GameObjectType,
+    life_system::LifeSystem, player_input::PlayerInput,
+};
 
 use super::PhysicsSystem;
 use eyre::Result;
-use ggez::{graphics::Rect, Context};
+use ggez::graphics::Rect;
 
 #[derive(Debug)]
 pub struct HeartPhysics {
@@ -21,13 +24,15 @@ impl PhysicsSystem for HeartPhysics {
         location: &mut Rect,
         _screen_size: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
+        _input: &PlayerInput,
         collidable_game_objects: &[GameObject],
         _rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()> {
-        self.velocity_y += gravity_force / 4.0;
-        location.y += self.velocity_y;
+        let delta = clock.get_delta().as_secs_f32();
+        self.velocity_y += gravity_force / 4.0 * delta;
+        location.y += self.velocity_y * delta;
 
         if let Some(player) = collidable_game_objects
             .iter()
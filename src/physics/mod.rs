@@ -5,13 +5,12 @@ mod snake_physics;
 mod sword_physics;
 mod timer_physics;
 
-use crate::life_system::LifeSystem;
+use crate::{game_clock::GameClock, life_system::LifeSystem, player_input::PlayerInput};
 
 use super::{Chatter, GameObject};
 use eyre::Result;
 pub use fire_physics::FirePhysics;
 use ggez::graphics::Rect;
-use ggez::Context;
 pub use heart_physics::HeartPhysics;
 pub use player_physics::PlayerPhysics;
 pub use snake_physics::SnakePhysics;
@@ -28,10 +27,11 @@ where
         location: &mut Rect,
         screen_size: (f32, f32),
         gravity_force: f32,
-        context: &mut Context,
+        input: &PlayerInput,
         collidable_game_objects: &[GameObject],
         rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()>;
 
     fn get_velocity_x(&self) -> f32;
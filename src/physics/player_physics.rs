@@ -1,55 +1,66 @@
-use crate::{game_object_type::GameObjectType, life_system::LifeSystem};
+use crate::{
+    config::PlayerConfig, game_clock::GameClock, game_event::GameEvent,
+    game_object_type::GameObjectType, life_system::LifeSystem, player_input::PlayerInput,
+};
 
 use super::{Chatter, GameObject, PhysicsSystem};
 use eyre::Result;
-use ggez::audio;
-use ggez::audio::SoundSource;
 use ggez::graphics::Rect;
-use ggez::input::keyboard::KeyCode;
 use ggez::nalgebra::Point2;
-use ggez::{input, Context};
 use std::sync::mpsc::Sender;
 
-const MOVE_FORCE: f32 = 2.0;
-const JUMP_FORCE: f32 = -12.5;
-const FRICTION: f32 = 0.15;
 const DEFAULT_CHATTER_NAME: &str = "Unknown Player";
 
 #[derive(Debug)]
 pub struct PlayerPhysics {
     velocity: Point2<f32>,
     affected_by_gravity: bool,
-    player_hit_object: Sender<Chatter>,
-    heart_sound: audio::Source,
-    jump_sound: audio::Source,
-    hit_sound: audio::Source,
+    send_event: Sender<GameEvent>,
+    move_force: f32,
+    jump_force: f32,
+    friction: f32,
 }
 
 impl PlayerPhysics {
-    pub fn new(context: &mut Context, player_hit_object: Sender<Chatter>) -> PlayerPhysics {
+    pub fn new(send_event: Sender<GameEvent>, config: &PlayerConfig) -> PlayerPhysics {
         PlayerPhysics {
             velocity: Point2::new(0.0, 0.0),
             affected_by_gravity: true,
-            player_hit_object,
-            heart_sound: audio::Source::new(context, "/phaserUp7.ogg").unwrap(),
-            jump_sound: audio::Source::new(context, "/phaseJump1.ogg").unwrap(),
-            hit_sound: audio::Source::new(context, "/zap2.ogg").unwrap(),
+            send_event,
+            move_force: config.move_force,
+            jump_force: config.jump_force,
+            friction: config.friction,
         }
     }
 
-    fn handle_input(&mut self, context: &mut Context) {
-        if input::keyboard::is_key_pressed(context, KeyCode::A) {
-            self.velocity.x -= MOVE_FORCE;
-        } else if input::keyboard::is_key_pressed(context, KeyCode::S)
-            || input::keyboard::is_key_pressed(context, KeyCode::D)
-        {
-            self.velocity.x += MOVE_FORCE;
+    fn handle_input(&mut self, input: &PlayerInput, delta: f32) -> Result<()> {
+        let acceleration = if input.left {
+            -self.move_force
+        } else if input.right {
+            self.move_force
+        } else {
+            0.0
+        };
+        self.accelerate_x(acceleration, delta);
+
+        if input.jump && self.on_ground() {
+            self.send_event.send(GameEvent::PlayerJumped)?;
+            self.velocity.y -= self.jump_force;
+            self.affected_by_gravity = true;
         }
 
-        if input::keyboard::is_key_pressed(context, KeyCode::Space) && self.on_ground() {
-            self.jump_sound.play().unwrap();
-            self.velocity.y += JUMP_FORCE;
-            self.affected_by_gravity = true;
+        Ok(())
+    }
+
+    /// Speeds up towards the top speed the friction allows, worked out exactly for the time
+    /// that passed so the player moves the same however that time is split into updates
+    fn accelerate_x(&mut self, acceleration: f32, delta: f32) {
+        if self.friction > 0.0 {
+            let top_speed = acceleration / self.friction;
+            self.velocity.x =
+                top_speed + (self.velocity.x - top_speed) * (-self.friction * delta).exp();
+        } else {
+            self.velocity.x += acceleration * delta;
         }
     }
 
@@ -93,42 +104,37 @@ impl PhysicsSystem for PlayerPhysics {
         location: &mut Rect,
         arena: (f32, f32),
         gravity_force: f32,
-        context: &mut Context,
+        input: &PlayerInput,
         collidable_game_objects: &[GameObject],
         _rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()> {
-        self.handle_input(context);
+        let delta = clock.get_delta().as_secs_f32();
+        self.handle_input(input, delta)?;
         self.stay_in_arena(location, arena);
 
         if let Some(game_object) = self.get_colliding_with(collidable_game_objects, location) {
             if let Some(player_life_system) = life_system.as_deref_mut() {
                 if GameObjectType::Heart == game_object.my_type {
                     player_life_system.gain_life();
-                    self.heart_sound.play().unwrap();
-                } else if player_life_system.hit() {
-                    self.hit_sound.play().unwrap();
+                    self.send_event.send(GameEvent::PlayerHealed)?;
+                } else if player_life_system.hit_for(game_object.damage) {
                     let chatter = if let Some(chatter) = game_object.chatter {
                         chatter
                     } else {
                         Chatter::new(DEFAULT_CHATTER_NAME.to_owned(), (255, 255, 255), false)
                     };
-                    self.player_hit_object.send(chatter)?;
+                    self.send_event.send(GameEvent::PlayerHit(chatter))?;
                 }
             }
         }
 
         if self.affected_by_gravity {
-            self.velocity.y += gravity_force;
-        }
-        location.x += self.velocity.x;
-        location.y += self.velocity.y;
-
-        if self.velocity.x != 0.0 {
-            let opposite_velocity = self.velocity.x * -1.0;
-            let speed_decrease = opposite_velocity * FRICTION;
-            self.velocity.x += speed_decrease
+            self.velocity.y += gravity_force * delta;
         }
+        location.x += self.velocity.x * delta;
+        location.y += self.velocity.y * delta;
 
         Ok(())
     }
@@ -1,11 +1,15 @@
 use eyre::Result;
-use ggez::{graphics::Rect, Context};
+use ggez::graphics::Rect;
 
-use crate::{game_object::GameObject, game_object_type::GameObjectType, life_system::LifeSystem};
+use crate::{
+    game_clock::GameClock, game_object::GameObject, game_object_type::GameObjectType,
+    life_system::LifeSystem, player_input::PlayerInput,
+};
 
 use super::PhysicsSystem;
 
-const SNAKE_SPEED: f32 = 10.0;
+/// pixels per second
+const SNAKE_SPEED: f32 = 600.0;
 
 #[derive(Debug)]
 pub struct SnakePhysics {
@@ -44,18 +48,20 @@ impl PhysicsSystem for SnakePhysics {
         sprite: &mut Rect,
         screen_size: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
+        _input: &PlayerInput,
         collidable_game_objects: &[GameObject],
         rotation: &mut f32,
         _life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()> {
+        let delta = clock.get_delta().as_secs_f32();
         if self.affected_by_gravity {
-            self.velocity_y += gravity_force;
+            self.velocity_y += gravity_force * delta;
             *rotation = std::f32::consts::FRAC_PI_3 + 0.3;
         }
 
-        sprite.y += self.velocity_y;
-        sprite.x += self.velocity_x;
+        sprite.y += self.velocity_y * delta;
+        sprite.x += self.velocity_x * delta;
 
         if sprite.y + sprite.h > screen_size.1 {
             sprite.y = screen_size.1 - sprite.h;
@@ -1,20 +1,25 @@
 use super::PhysicsSystem;
-use crate::{life_system::LifeSystem, GameObject};
+use crate::{
+    game_clock::GameClock, life_system::LifeSystem, player_input::PlayerInput, GameObject,
+};
 use eyre::Result;
 use ggez::graphics::Rect;
-use ggez::Context;
 
 #[derive(Debug)]
 pub struct SwordPhysics {
     velocity_x: f32,
     velocity_y: f32,
+    bounce_velocity_x: f32,
 }
 
 impl SwordPhysics {
-    pub fn new() -> SwordPhysics {
+    /// The bounce velocity is how fast the sword moves sideways after first hitting the ground,
+    /// in pixels per second
+    pub fn new(bounce_velocity_x: f32) -> SwordPhysics {
         SwordPhysics {
             velocity_x: 0.0,
             velocity_y: 0.0,
+            bounce_velocity_x,
         }
     }
 
@@ -33,14 +38,16 @@ impl PhysicsSystem for SwordPhysics {
         location: &mut Rect,
         screen_size: (f32, f32),
         gravity_force: f32,
-        _context: &mut Context,
+        _input: &PlayerInput,
         _collidable_game_objects: &[GameObject],
         rotation: &mut f32,
         life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()> {
-        self.velocity_y += gravity_force;
-        location.y += self.velocity_y;
-        location.x += self.velocity_x;
+        let delta = clock.get_delta().as_secs_f32();
+        self.velocity_y += gravity_force * delta;
+        location.y += self.velocity_y * delta;
+        location.x += self.velocity_x * delta;
         *rotation = self.calculate_rotation();
 
         if let Some(sword_life_system) = life_system.as_deref_mut() {
@@ -50,7 +57,7 @@ impl PhysicsSystem for SwordPhysics {
                     self.velocity_y *= -0.9;
 
                     if self.is_first_fall() {
-                        self.velocity_x = rand::random::<f32>() * 15.0;
+                        self.velocity_x = self.bounce_velocity_x;
                     }
 
                     sword_life_system.hit();
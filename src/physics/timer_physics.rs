@@ -1,23 +1,19 @@
-use crate::life_system::LifeSystem;
+use crate::{game_clock::GameClock, life_system::LifeSystem, player_input::PlayerInput};
 
 use super::{GameObject, PhysicsSystem};
 use eyre::Result;
 use ggez::graphics::Rect;
-use ggez::Context;
 use std::time::Duration;
 
 #[derive(Debug)]
 pub struct TimerPhysicsSystem {
-    velocity_y: f32,
+    pixels_per_second: f32,
 }
 
 impl TimerPhysicsSystem {
-    pub fn new(timer_size: f32, game_time: Duration, fps: f32) -> TimerPhysicsSystem {
+    pub fn new(timer_size: f32, game_time: Duration) -> TimerPhysicsSystem {
         let pixels_per_second = timer_size / (game_time.as_secs_f32() - 0.2); //if i understand this correctly, this is 0.2 because that is the height of the drop zone labels at the top
-        let pixels_per_frame = pixels_per_second / fps;
-        TimerPhysicsSystem {
-            velocity_y: pixels_per_frame,
-        }
+        TimerPhysicsSystem { pixels_per_second }
     }
 }
 
@@ -27,12 +23,14 @@ impl PhysicsSystem for TimerPhysicsSystem {
         location: &mut Rect,
         _screen_size: (f32, f32),
         _gravity_force: f32,
-        _context: &mut Context,
+        _input: &PlayerInput,
         _collidable_game_objects: &[GameObject],
         _rotation: &mut f32,
         _life_system: &mut Option<Box<dyn LifeSystem>>,
+        clock: &GameClock,
     ) -> Result<()> {
-        location.y += self.velocity_y;
+        // follows game time so the timer slows down, speeds up and stops along with the round
+        location.y += self.pixels_per_second * clock.get_delta().as_secs_f32();
 
         Ok(())
     }
@@ -0,0 +1,7 @@
+/// The keys the streamer is holding down during a single update tick
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerInput {
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+}
@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+use eyre::{eyre, Result};
+
+use crate::{
+    chatter::{Chatter, ChatterRole},
+    player_input::PlayerInput,
+};
+
+/// Writes everything needed to play a round again: the seed, then every chat message and
+/// the keys held down, each tagged with the simulation tick they were handed to the game on.
+///
+/// ```text
+/// seed 1234
+/// chat 900 12345 Alice 255 0 0 true viewer #fire 3
+/// input 900 0 1 0
+/// pause 960 1
+/// ```
+pub struct ReplayRecorder<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> ReplayRecorder<W> {
+    pub fn new(mut writer: W, seed: u64) -> io::Result<Self> {
+        writeln!(writer, "seed {}", seed)?;
+        Ok(Self { writer })
+    }
+
+    pub fn record_chat_message(
+        &mut self,
+        tick: u32,
+        chatter: &Chatter,
+        message: &str,
+    ) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "chat {} {} {} {} {} {} {} {} {}",
+            tick,
+            chatter.user_id,
+            chatter.name,
+            chatter.red,
+            chatter.green,
+            chatter.blue,
+            chatter.is_subscriber,
+            chatter.role.as_str(),
+            message
+        )
+    }
+
+    /// The streamer pausing or resuming the game from the keyboard or by leaving the window
+    pub fn record_pause(&mut self, tick: u32, is_paused: bool) -> io::Result<()> {
+        writeln!(self.writer, "pause {} {}", tick, is_paused as u8)
+    }
+
+    /// Inputs are recorded every tick, so this is also where the recording is flushed to make
+    /// sure it survives the game crashing
+    pub fn record_input(&mut self, tick: u32, input: &PlayerInput) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "input {} {} {} {}",
+            tick, input.left as u8, input.right as u8, input.jump as u8
+        )?;
+        self.writer.flush()
+    }
+}
+
+/// A recorded round that can be fed back into the game instead of chat and the keyboard
+#[derive(Debug)]
+pub struct Replay {
+    pub seed: u64,
+    chat_messages: VecDeque<(u32, Chatter, String)>,
+    inputs: VecDeque<(u32, PlayerInput)>,
+    pauses: VecDeque<(u32, bool)>,
+}
+
+impl Replay {
+    pub fn load(reader: impl BufRead) -> Result<Replay> {
+        let mut seed = None;
+        let mut chat_messages = VecDeque::new();
+        let mut inputs = VecDeque::new();
+        let mut pauses = VecDeque::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            let mut parts = line.splitn(2, ' ');
+            let kind = parts.next().unwrap_or_default();
+            let rest = parts.next().unwrap_or_default();
+            match kind {
+                "seed" => seed = Some(parse(rest, "seed", line_number)?),
+                "chat" => chat_messages.push_back(Self::parse_chat_message(rest, line_number)?),
+                "input" => inputs.push_back(Self::parse_input(rest, line_number)?),
+                "pause" => pauses.push_back(Self::parse_pause(rest, line_number)?),
+                "" => {}
+                _ => {
+                    return Err(eyre!(
+                        "line {}: unknown replay entry '{}'",
+                        line_number,
+                        kind
+                    ))
+                }
+            }
+        }
+
+        Ok(Replay {
+            seed: seed.ok_or_else(|| eyre!("the replay is missing its seed"))?,
+            chat_messages,
+            inputs,
+            pauses,
+        })
+    }
+
+    /// Every chat message that was handed to the game right before the given tick
+    pub fn take_chat_messages(&mut self, tick: u32) -> Vec<(Chatter, String)> {
+        let mut chat_messages = vec![];
+        while let Some((message_tick, _, _)) = self.chat_messages.front() {
+            if *message_tick > tick {
+                break;
+            }
+            if let Some((_, chatter, message)) = self.chat_messages.pop_front() {
+                chat_messages.push((chatter, message));
+            }
+        }
+        chat_messages
+    }
+
+    /// Whether the game was paused or resumed right before the given tick, in order
+    pub fn take_pauses(&mut self, tick: u32) -> Vec<bool> {
+        let mut pauses = vec![];
+        while let Some((pause_tick, is_paused)) = self.pauses.front() {
+            if *pause_tick > tick {
+                break;
+            }
+            pauses.push(*is_paused);
+            self.pauses.pop_front();
+        }
+        pauses
+    }
+
+    /// The keys held down on the given tick, nothing is pressed once the recording runs out
+    pub fn take_input(&mut self, tick: u32) -> PlayerInput {
+        while let Some((input_tick, input)) = self.inputs.pop_front() {
+            if input_tick == tick {
+                return input;
+            } else if input_tick > tick {
+                self.inputs.push_front((input_tick, input));
+                break;
+            }
+        }
+        PlayerInput::default()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.chat_messages.is_empty() && self.inputs.is_empty() && self.pauses.is_empty()
+    }
+
+    fn parse_chat_message(entry: &str, line_number: usize) -> Result<(u32, Chatter, String)> {
+        let parts: Vec<&str> = entry.splitn(9, ' ').collect();
+        if parts.len() < 9 {
+            return Err(eyre!("line {}: chat entries need 9 fields", line_number));
+        }
+        let role = ChatterRole::parse(parts[7])
+            .ok_or_else(|| eyre!("line {}: '{}' is not a valid role", line_number, parts[7]))?;
+        let chatter = Chatter::new(
+            parts[2].to_owned(),
+            (
+                parse(parts[3], "red", line_number)?,
+                parse(parts[4], "green", line_number)?,
+                parse(parts[5], "blue", line_number)?,
+            ),
+            parse(parts[6], "subscriber", line_number)?,
+        )
+        .with_user_id(parts[1].to_owned())
+        .with_role(role);
+        Ok((
+            parse(parts[0], "tick", line_number)?,
+            chatter,
+            parts[8].to_owned(),
+        ))
+    }
+
+    fn parse_pause(entry: &str, line_number: usize) -> Result<(u32, bool)> {
+        let parts: Vec<&str> = entry.split(' ').collect();
+        if parts.len() != 2 {
+            return Err(eyre!("line {}: pause entries need 2 fields", line_number));
+        }
+        Ok((
+            parse(parts[0], "tick", line_number)?,
+            parse::<u8>(parts[1], "pause", line_number)? == 1,
+        ))
+    }
+
+    fn parse_input(entry: &str, line_number: usize) -> Result<(u32, PlayerInput)> {
+        let parts: Vec<&str> = entry.split(' ').collect();
+        if parts.len() != 4 {
+            return Err(eyre!("line {}: input entries need 4 fields", line_number));
+        }
+        let input = PlayerInput {
+            left: parse::<u8>(parts[1], "left", line_number)? == 1,
+            right: parse::<u8>(parts[2], "right", line_number)? == 1,
+            jump: parse::<u8>(parts[3], "jump", line_number)? == 1,
+        };
+        Ok((parse(parts[0], "tick", line_number)?, input))
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str, name: &str, line_number: usize) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| eyre!("line {}: '{}' is not a valid {}", line_number, value, name))
+}
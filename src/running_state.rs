@@ -1,7 +1,9 @@
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum RunningState {
     StartingSoon,
     Playing,
+    /// a moderator or the streamer stopped the game, the round's timer doesn't run while paused
+    Paused,
     PlayerWon,
     ChatWon,
 }
@@ -9,7 +11,7 @@ pub enum RunningState {
 impl RunningState {
     pub fn is_game_over(&self) -> bool {
         match self {
-            RunningState::Playing | RunningState::StartingSoon => false,
+            RunningState::Playing | RunningState::StartingSoon | RunningState::Paused => false,
             RunningState::PlayerWon | RunningState::ChatWon => true,
         }
     }
@@ -0,0 +1,310 @@
+use std::io::BufRead;
+
+use eyre::{eyre, Result};
+
+use crate::{config::Config, harness::Harness, running_state::RunningState};
+
+/// A round scripted ahead of time, played headless against the simulation and checked
+/// against expectations so tests can catch changes to commands, scoring and who wins.
+///
+/// Times are counted from when the round starts, after the splash screen, either in
+/// seconds with `t=` or in ticks with `tick=`. Expectations without a time are checked
+/// once the round is over.
+///
+/// ```text
+/// # comments start with a hash
+/// seed 42
+/// t=3.0 alice #sword 4
+/// tick=200 press right
+/// t=4.0 release right
+/// t=5.0 expect score alice 1
+/// expect chat "Hit! alice gets 10 points"
+/// expect winner chat
+/// ```
+#[derive(Debug)]
+pub struct Scenario {
+    seed: u64,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug)]
+struct Step {
+    line_number: usize,
+    at: Option<At>,
+    action: Action,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum At {
+    Seconds(f64),
+    Tick(u32),
+}
+
+#[derive(Debug)]
+enum Action {
+    Chat(String, String),
+    Press(Key),
+    Release(Key),
+    Expect(Expectation),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Key {
+    Left,
+    Right,
+    Jump,
+}
+
+#[derive(Debug)]
+enum Expectation {
+    Chat(String),
+    Score(String, u128),
+    Lives(u8),
+    Winner(RunningState),
+}
+
+impl Scenario {
+    pub fn load(reader: impl BufRead) -> Result<Scenario> {
+        let mut seed = 0;
+        let mut steps = vec![];
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("seed ") {
+                seed = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| eyre!("line {}: '{}' is not a valid seed", line_number, value))?;
+                continue;
+            }
+            let (at, rest) = Self::parse_at(line, line_number)?;
+            let action = Self::parse_action(rest, line_number)?;
+            if at.is_none() && !matches!(action, Action::Expect(_)) {
+                return Err(eyre!(
+                    "line {}: only expectations can leave out when they happen",
+                    line_number
+                ));
+            }
+            steps.push(Step {
+                line_number,
+                at,
+                action,
+            });
+        }
+
+        Ok(Scenario { seed, steps })
+    }
+
+    /// Play the scenario from start to finish, the error lists every expectation that wasn't met
+    pub fn run(&self, config: Config) -> Result<()> {
+        let framerate_target = config.world.framerate_target;
+        let mut harness = Harness::started(config, self.seed);
+        let round_started_at_tick = harness.get_simulation().get_ticks();
+
+        let mut timed_steps: Vec<(u32, &Step)> = self
+            .steps
+            .iter()
+            .filter_map(|step| {
+                let tick = match step.at? {
+                    At::Seconds(seconds) => (seconds * framerate_target as f64).round() as u32,
+                    At::Tick(tick) => tick,
+                };
+                Some((round_started_at_tick + tick, step))
+            })
+            .collect();
+        timed_steps.sort_by_key(|(tick, _)| *tick);
+
+        let mut failures = vec![];
+        for (tick, step) in timed_steps {
+            while harness.get_simulation().get_ticks() < tick
+                && !harness.get_running_state().is_game_over()
+            {
+                harness.run_ticks(1);
+            }
+            if let Err(failure) = Self::apply(&mut harness, &step.action) {
+                failures.push(format!("line {}: {}", step.line_number, failure));
+            }
+        }
+
+        harness.finish_round();
+        for step in self.steps.iter().filter(|step| step.at.is_none()) {
+            if let Err(failure) = Self::apply(&mut harness, &step.action) {
+                failures.push(format!("line {}: {}", step.line_number, failure));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre!(failures.join("\n")))
+        }
+    }
+
+    fn parse_at(line: &str, line_number: usize) -> Result<(Option<At>, &str)> {
+        let mut parts = line.splitn(2, ' ');
+        let first = parts.next().unwrap_or_default();
+        let rest = parts.next().unwrap_or_default().trim();
+        let at = if let Some(seconds) = first.strip_prefix("t=") {
+            At::Seconds(
+                seconds.parse().map_err(|_| {
+                    eyre!("line {}: '{}' is not a valid time", line_number, seconds)
+                })?,
+            )
+        } else if let Some(tick) = first.strip_prefix("tick=") {
+            At::Tick(
+                tick.parse()
+                    .map_err(|_| eyre!("line {}: '{}' is not a valid tick", line_number, tick))?,
+            )
+        } else {
+            return Ok((None, line));
+        };
+        Ok((Some(at), rest))
+    }
+
+    fn parse_action(line: &str, line_number: usize) -> Result<Action> {
+        let mut parts = line.splitn(2, ' ');
+        let first = parts.next().unwrap_or_default();
+        let rest = parts.next().unwrap_or_default().trim();
+        match first {
+            "press" => Ok(Action::Press(Self::parse_key(rest, line_number)?)),
+            "release" => Ok(Action::Release(Self::parse_key(rest, line_number)?)),
+            "expect" => Ok(Action::Expect(Self::parse_expectation(rest, line_number)?)),
+            "" => Err(eyre!("line {}: nothing to do", line_number)),
+            _ if rest.is_empty() => Err(eyre!(
+                "line {}: {} needs a message to send to chat",
+                line_number,
+                first
+            )),
+            name => Ok(Action::Chat(name.to_owned(), rest.to_owned())),
+        }
+    }
+
+    fn parse_key(key: &str, line_number: usize) -> Result<Key> {
+        match key {
+            "left" => Ok(Key::Left),
+            "right" => Ok(Key::Right),
+            "jump" => Ok(Key::Jump),
+            _ => Err(eyre!(
+                "line {}: '{}' is not a key, use left, right or jump",
+                line_number,
+                key
+            )),
+        }
+    }
+
+    fn parse_expectation(expectation: &str, line_number: usize) -> Result<Expectation> {
+        let parts: Vec<&str> = expectation.splitn(2, ' ').collect();
+        match parts.as_slice() {
+            ["chat", message] => {
+                let message = message
+                    .strip_prefix('"')
+                    .and_then(|message| message.strip_suffix('"'))
+                    .ok_or_else(|| {
+                        eyre!("line {}: chat messages need to be in quotes", line_number)
+                    })?;
+                Ok(Expectation::Chat(message.to_owned()))
+            }
+            ["score", score] => {
+                let score: Vec<&str> = score.split(' ').collect();
+                match score.as_slice() {
+                    [name, points] => Ok(Expectation::Score(
+                        (*name).to_owned(),
+                        points.parse().map_err(|_| {
+                            eyre!("line {}: '{}' is not a valid score", line_number, points)
+                        })?,
+                    )),
+                    _ => Err(eyre!(
+                        "line {}: expected a name and the points, like score alice 10",
+                        line_number
+                    )),
+                }
+            }
+            ["lives", lives] => Ok(Expectation::Lives(lives.parse().map_err(|_| {
+                eyre!(
+                    "line {}: '{}' is not a valid number of lives",
+                    line_number,
+                    lives
+                )
+            })?)),
+            ["winner", "chat"] => Ok(Expectation::Winner(RunningState::ChatWon)),
+            ["winner", "streamer"] => Ok(Expectation::Winner(RunningState::PlayerWon)),
+            ["winner", winner] => Err(eyre!(
+                "line {}: the winner is either chat or streamer, not '{}'",
+                line_number,
+                winner
+            )),
+            _ => Err(eyre!(
+                "line {}: unknown expectation '{}'",
+                line_number,
+                expectation
+            )),
+        }
+    }
+
+    fn apply(harness: &mut Harness, action: &Action) -> Result<(), String> {
+        match action {
+            Action::Chat(name, message) => harness.chat(name, message),
+            Action::Press(key) => Self::set_key(harness, *key, true),
+            Action::Release(key) => Self::set_key(harness, *key, false),
+            Action::Expect(expectation) => Self::check(harness, expectation)?,
+        }
+        Ok(())
+    }
+
+    fn set_key(harness: &mut Harness, key: Key, is_pressed: bool) {
+        let mut input = harness.get_input();
+        match key {
+            Key::Left => input.left = is_pressed,
+            Key::Right => input.right = is_pressed,
+            Key::Jump => input.jump = is_pressed,
+        }
+        harness.set_input(input);
+    }
+
+    fn check(harness: &Harness, expectation: &Expectation) -> Result<(), String> {
+        match expectation {
+            Expectation::Chat(message) => {
+                let chat_log = harness.get_chat_log();
+                if !chat_log.contains(&message.as_str()) {
+                    return Err(format!(
+                        "expected chat to be told \"{}\", but it was told {:?}",
+                        message, chat_log
+                    ));
+                }
+            }
+            Expectation::Score(name, points) => {
+                let score = harness.get_score(name);
+                if score != *points {
+                    return Err(format!(
+                        "expected {} to have {} points, but they have {}",
+                        name, points, score
+                    ));
+                }
+            }
+            Expectation::Lives(lives) => {
+                let lives_left = harness.get_player_lives_left();
+                if lives_left != *lives {
+                    return Err(format!(
+                        "expected the streamer to have {} lives, but they have {}",
+                        lives, lives_left
+                    ));
+                }
+            }
+            Expectation::Winner(winner) => {
+                let running_state = harness.get_running_state();
+                if running_state != *winner {
+                    return Err(format!(
+                        "expected the round to end with {:?}, but it is {:?}",
+                        winner, running_state
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+pub const POINTS_FOR_DROP: u128 = 1;
+pub const POINTS_FOR_HIT: u128 = 10;
+
+/// A chatter's points along with the name they were last seen with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    pub name: String,
+    pub points: u128,
+}
+
+impl Score {
+    pub fn new(name: String, points: u128) -> Score {
+        Score { name, points }
+    }
+}
@@ -0,0 +1,249 @@
+use ggez::graphics::{
+    self, Align, Color, DrawMode, DrawParam, Font, Mesh, MeshBuilder, Rect, Scale, Text,
+};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+use crate::object_registry::{CommandHelp, ObjectRegistry};
+use crate::score::{POINTS_FOR_DROP, POINTS_FOR_HIT};
+use crate::sprites::{Sprite, Sprites};
+
+/// how much of the window's width the sidebar takes, the 400 pixels the old image took
+/// on a 1920 wide window
+const WIDTH_SHARE: f32 = 400.0 / 1920.0;
+/// the window height the sizes below are picked for, other heights scale them
+const LAYOUT_HEIGHT: f32 = 1080.0;
+const MARGIN: f32 = 20.0;
+const TITLE_SIZE: f32 = 50.0;
+const HEADING_SIZE: f32 = 30.0;
+const COMMAND_SIZE: f32 = 26.0;
+const DESCRIPTION_SIZE: f32 = 20.0;
+const ICON_SIZE: f32 = 40.0;
+/// room at the bottom for the streamer's lives
+const LIVES_HEIGHT: f32 = 90.0;
+
+/// An object's sprite next to its commands, which stays on its first frame
+struct Icon {
+    sprite: Sprite,
+    location: Point2<f32>,
+    scale: f32,
+}
+
+/// The instructions next to the arena, laid out from the commands in the object registry
+/// so new objects and aliases show up without editing an image
+pub struct Sidebar {
+    pub width: f32,
+    x: f32,
+    background: Mesh,
+    texts: Vec<(Text, Point2<f32>, Color)>,
+    icons: Vec<Icon>,
+    /// room left between the commands and the lives, for the drop panel
+    pub drop_panel_area: Rect,
+    pub hearts_y: f32,
+    /// sizes are given for a 1080 high window and scaled by this
+    size_scale: f32,
+}
+
+impl Sidebar {
+    pub fn new(
+        context: &mut Context,
+        screen_size: (f32, f32),
+        objects: &ObjectRegistry,
+        sprites: &Sprites,
+        drop_zone_count: u8,
+    ) -> GameResult<Sidebar> {
+        let (screen_width, screen_height) = screen_size;
+        let width = Self::width_for(screen_width);
+        let x = screen_width - width;
+        let background = MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                Rect::new(x, 0.0, width, screen_height),
+                Color::new(0.1, 0.1, 0.18, 1.0),
+            )
+            .build(context)?;
+        let mut sidebar = Sidebar {
+            width,
+            x,
+            background,
+            texts: vec![],
+            icons: vec![],
+            drop_panel_area: Rect::new(x, 0.0, width, 0.0),
+            hearts_y: 0.0,
+            size_scale: screen_height / LAYOUT_HEIGHT,
+        };
+
+        let mut y = sidebar.size(MARGIN);
+        y += sidebar.add_centered_text(context, "Get the Streamer Game", TITLE_SIZE, y);
+        let scoring = format!(
+            "{} point for every drop, {} for hitting the streamer",
+            POINTS_FOR_DROP, POINTS_FOR_HIT
+        );
+        y += sidebar.add_centered_text(context, &scoring, DESCRIPTION_SIZE, y);
+
+        let command_help = objects.get_command_help();
+        let (helps, attacks): (Vec<_>, Vec<_>) = command_help
+            .iter()
+            .partition(|command_help| command_help.helps_streamer);
+        y += sidebar.size(MARGIN);
+        y += sidebar.add_centered_text(context, "Commands to Attack Streamer", HEADING_SIZE, y);
+        let targets = format!(
+            "follow with a column from 0 to {} or player, left, right, far or center",
+            drop_zone_count.saturating_sub(1)
+        );
+        y += sidebar.add_centered_text(context, &targets, DESCRIPTION_SIZE, y);
+        for command_help in attacks {
+            y += sidebar.add_command(context, command_help, sprites, y);
+        }
+        if !helps.is_empty() {
+            y += sidebar.size(MARGIN);
+            y += sidebar.add_centered_text(context, "Commands to Help Streamer", HEADING_SIZE, y);
+            for command_help in helps {
+                y += sidebar.add_command(context, command_help, sprites, y);
+            }
+        }
+
+        let lives_y = screen_height - sidebar.size(LIVES_HEIGHT);
+        let lives_heading_height =
+            sidebar.add_centered_text(context, "Streamer Lives Left", HEADING_SIZE, lives_y);
+        sidebar.hearts_y = lives_y + lives_heading_height + sidebar.size(5.0);
+        let drop_panel_y = y + sidebar.size(MARGIN);
+        sidebar.drop_panel_area = Rect::new(
+            x,
+            drop_panel_y,
+            width,
+            (lives_y - drop_panel_y - sidebar.size(MARGIN)).max(0.0),
+        );
+
+        Ok(sidebar)
+    }
+
+    /// How wide the sidebar is in a window this wide, the arena gets the rest
+    pub fn width_for(screen_width: f32) -> f32 {
+        (screen_width * WIDTH_SHARE).round()
+    }
+
+    pub fn draw(&self, context: &mut Context) -> GameResult<()> {
+        graphics::draw(context, &self.background, DrawParam::new())?;
+        for icon in &self.icons {
+            icon.sprite
+                .draw(context, icon.location, [icon.scale, icon.scale], &0.0, None)?;
+        }
+        for (text, location, color) in &self.texts {
+            graphics::draw(
+                context,
+                text,
+                DrawParam::new().dest(*location).color(*color),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// A size picked for a 1080 high window, scaled to this one
+    fn size(&self, size: f32) -> f32 {
+        size * self.size_scale
+    }
+
+    /// An icon with the command and its aliases next to it and the description underneath,
+    /// returning how much room it took
+    fn add_command(
+        &mut self,
+        context: &mut Context,
+        command_help: &CommandHelp,
+        sprites: &Sprites,
+        y: f32,
+    ) -> f32 {
+        let icon_size = self.size(ICON_SIZE);
+        let icon_x = self.x + self.size(MARGIN);
+        let sprite = command_help
+            .object
+            .and_then(|object| sprites.get_object(&object.name));
+        match sprite {
+            Some(sprite) => self.icons.push(Icon {
+                sprite: sprite.clone(),
+                location: Point2::new(icon_x, y),
+                scale: icon_size / sprite.width.max(sprite.height),
+            }),
+            None => {
+                self.add_text(
+                    context,
+                    "?",
+                    COMMAND_SIZE,
+                    icon_x,
+                    icon_size,
+                    y,
+                    Align::Center,
+                );
+            }
+        }
+
+        let text_x = icon_x + icon_size + self.size(MARGIN / 2.0);
+        let text_width = self.x + self.width - text_x - self.size(MARGIN);
+        let mut command = format!("{} <column>", command_help.command);
+        if !command_help.aliases.is_empty() {
+            command.push_str(&format!("  also {}", command_help.aliases.join(" ")));
+        }
+        let mut height = self.add_text(
+            context,
+            &command,
+            COMMAND_SIZE,
+            text_x,
+            text_width,
+            y,
+            Align::Left,
+        );
+        if !command_help.description.is_empty() {
+            height += self.add_text(
+                context,
+                command_help.description,
+                DESCRIPTION_SIZE,
+                text_x,
+                text_width,
+                y + height,
+                Align::Left,
+            );
+        }
+        height.max(icon_size) + self.size(MARGIN / 2.0)
+    }
+
+    fn add_centered_text(&mut self, context: &mut Context, text: &str, size: f32, y: f32) -> f32 {
+        let margin = self.size(MARGIN);
+        let width = self.width - margin * 2.0;
+        self.add_text(
+            context,
+            text,
+            size,
+            self.x + margin,
+            width,
+            y,
+            Align::Center,
+        )
+    }
+
+    /// Wrap the text to the width given, returning how high it ended up
+    #[allow(clippy::too_many_arguments)]
+    fn add_text(
+        &mut self,
+        context: &mut Context,
+        text: &str,
+        size: f32,
+        x: f32,
+        width: f32,
+        y: f32,
+        align: Align,
+    ) -> f32 {
+        let size = self.size(size);
+        let mut text = Text::new(text);
+        text.set_font(Font::default(), Scale::uniform(size));
+        text.set_bounds(Point2::new(width, f32::INFINITY), align);
+        // descriptions are a bit dimmer than everything else
+        let color = if size < self.size(COMMAND_SIZE) {
+            Color::new(0.8, 0.8, 0.8, 1.0)
+        } else {
+            graphics::WHITE
+        };
+        let height = (text.height(context) as f32).max(size);
+        self.texts.push((text, Point2::new(x, y), color));
+        height
+    }
+}
@@ -0,0 +1,769 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+use ggez::nalgebra::Point2;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    chatter::Chatter,
+    command::{Command, DropTarget},
+    command_queue::{CommandQueue, CommandQueueMetrics},
+    config::Config,
+    control_command::ControlCommand,
+    draw_system::{DrawSystem, PlayerDrawSystem},
+    drop_limiter::DropLimiter,
+    game_clock::GameClock,
+    game_event::GameEvent,
+    game_object::GameObject,
+    game_object_type::GameObjectType,
+    incoming_drop::IncomingDrop,
+    leaderboard::Leaderboard,
+    life_system::PlayerLifeSystem,
+    lobby::Lobby,
+    physics::PlayerPhysics,
+    player_input::PlayerInput,
+    running_state::RunningState,
+    score::{Score, POINTS_FOR_DROP, POINTS_FOR_HIT},
+    sprites::Sprites,
+    DROP_ZONE_HEIGHT,
+};
+
+/// the streamer's hitbox, which their 16 pixel sprites are scaled up to fill
+const PLAYER_SIZE: (f32, f32) = (64.0, 64.0);
+/// how many of the last hits on the streamer are kept for the sidebar
+const RECENT_HITS_KEPT: usize = 5;
+
+/// The rules of the game without any window, keyboard or speakers attached.
+///
+/// The simulation is advanced one fixed tick at a time with `update` and reports
+/// everything the outside world should know about through `GameEvent`s.
+pub struct Simulation {
+    config: Config,
+    arena_size: (f32, f32),
+    game_objects: Vec<GameObject>,
+    running_state: RunningState,
+    /// keyed by the chatters' user ids
+    scores: HashMap<String, Score>,
+    /// every finished round's scores added up, keyed by the chatters' user ids
+    session_scores: HashMap<String, Score>,
+    /// counting from 1
+    round: u32,
+    sprites: Option<Sprites>,
+    ticks: u32,
+    clock: GameClock,
+    /// in game time
+    state_started_at: Duration,
+    /// the state the game was paused in and when that state started in game time
+    paused: Option<(RunningState, Duration)>,
+    /// how fast the game runs outside of the finale, 1.0 is real time
+    time_scale: f32,
+    /// user ids of the chatters that are ignored for the rest of the session
+    banned: HashSet<String>,
+    /// the user id of everyone who has chatted this session by their lowercase name, so
+    /// moderators can ban chatters by name
+    seen_chatters: HashMap<String, String>,
+    events: Vec<GameEvent>,
+    receive_event: Receiver<GameEvent>,
+    leaderboard: Leaderboard,
+    drop_limiter: DropLimiter,
+    command_queue: CommandQueue,
+    /// waiting out the drop warning, in the order they will land
+    incoming_drops: Vec<IncomingDrop>,
+    /// the chatters that hit the streamer last, newest first
+    recent_hits: VecDeque<Chatter>,
+    lobby: Lobby,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Simulation {
+    /// Pass in `None` for the sprites to run without anything to draw, for example in tests.
+    /// Every random decision in the game comes from the seed, so playing the same seed
+    /// with the same chat messages and input results in the same round.
+    pub fn new(
+        arena_size: (f32, f32),
+        sprites: Option<Sprites>,
+        config: Config,
+        seed: u64,
+    ) -> Simulation {
+        let (send_event, receive_event) = channel();
+        let player = Self::create_player(sprites.as_ref(), &config, send_event);
+        let starting_soon_message = Self::get_starting_soon_message(&config);
+        let clock = GameClock::new(config.world.framerate_target);
+        let time_scale = config.world.time_scale;
+
+        Simulation {
+            config,
+            arena_size,
+            game_objects: vec![player],
+            running_state: RunningState::StartingSoon,
+            scores: HashMap::new(),
+            session_scores: HashMap::new(),
+            round: 1,
+            sprites,
+            ticks: 0,
+            clock,
+            state_started_at: Duration::default(),
+            paused: None,
+            time_scale,
+            banned: HashSet::new(),
+            seen_chatters: HashMap::new(),
+            events: vec![GameEvent::SendToChat(starting_soon_message)],
+            receive_event,
+            leaderboard: Leaderboard::default(),
+            drop_limiter: DropLimiter::default(),
+            command_queue: CommandQueue::default(),
+            incoming_drops: vec![],
+            recent_hits: VecDeque::new(),
+            lobby: Lobby::default(),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Handle a message from chat, drops are queued while the round is being played and
+    /// land over the next ticks. During the splash screen chatters can join the round and
+    /// send their first drop early, and they can ask about the leaderboard at any time.
+    pub fn handle_chat_message(&mut self, message: &str, chatter: Chatter) {
+        self.seen_chatters
+            .insert(chatter.name.to_lowercase(), chatter.user_id.clone());
+        if chatter.can_control_game() {
+            match ControlCommand::new(message) {
+                Ok(Some(command)) => return self.handle_control_command(command, &chatter),
+                Err(error) => return self.events.push(GameEvent::SendToChat(error.to_owned())),
+                Ok(None) => {}
+            }
+        } else if self.banned.contains(&chatter.user_id) {
+            return;
+        }
+
+        if let Some(answer) = self.leaderboard.handle_chat_message(
+            message,
+            &chatter,
+            &self.scores,
+            self.ticks,
+            &self.config,
+        ) {
+            self.events.push(GameEvent::SendToChat(answer));
+            return;
+        }
+
+        let is_starting_soon = match self.running_state {
+            RunningState::StartingSoon => true,
+            RunningState::Playing => false,
+            _ => return,
+        };
+        if is_starting_soon && Lobby::is_join_message(message) {
+            self.lobby.join(chatter);
+            return;
+        }
+
+        let (commands, problems) = Command::parse(
+            message,
+            &chatter,
+            &self.config.objects,
+            self.config.world.drop_zone_count,
+            &mut self.rng,
+        );
+        if !problems.is_empty() {
+            self.events.push(GameEvent::SendToChat(problems.join(" ")));
+        }
+        if commands.is_empty() {
+            return;
+        }
+
+        if is_starting_soon {
+            if let Some(message) = self.lobby.queue_first_drops(chatter, commands) {
+                self.events.push(GameEvent::SendToChat(message));
+            }
+            return;
+        }
+
+        self.queue_drops(&chatter, commands, false);
+    }
+
+    /// Queue as many of the drops from one message as the drop limits let the chatter drop.
+    /// Drops sent early during the splash screen don't count towards chat's drops per second.
+    fn queue_drops(&mut self, chatter: &Chatter, commands: Vec<Command>, sent_early: bool) {
+        let tick = self.clock.get_ticks();
+        let allowed = if sent_early {
+            self.drop_limiter
+                .allow_early_drops(chatter, commands.len(), tick, &self.config)
+        } else {
+            self.drop_limiter
+                .allow_drops(chatter, commands.len(), tick, &self.config)
+        };
+        match allowed {
+            Ok(allowed) => {
+                for command in commands.into_iter().take(allowed) {
+                    self.command_queue.push(command, tick, &self.config);
+                }
+            }
+            Err(Some(message)) => self.events.push(GameEvent::SendToChat(message)),
+            Err(None) => {}
+        }
+    }
+
+    /// Advance the game by a single tick, returning everything that happened since the last update
+    pub fn update(&mut self, input: &PlayerInput) -> Vec<GameEvent> {
+        self.ticks += 1;
+        let time_scale = if self.is_in_finale() {
+            self.time_scale * self.config.round.finale_time_scale
+        } else {
+            self.time_scale
+        };
+        self.clock.set_time_scale(time_scale);
+        self.clock.tick();
+
+        match self.running_state {
+            RunningState::StartingSoon => {
+                if self.get_time_in_running_state() >= self.config.round.splash_duration() {
+                    let players_needed = self.get_players_needed();
+                    if players_needed == 0 {
+                        self.start_playing();
+                    } else if self.lobby.start_waiting() {
+                        let message = format!(
+                            "Waiting for {}, type #join to play",
+                            describe_players(players_needed)
+                        );
+                        self.events.push(GameEvent::SendToChat(message));
+                    }
+                }
+            }
+            RunningState::Playing => self.update_round(input),
+            RunningState::ChatWon | RunningState::PlayerWon => {
+                if !self.is_session_over()
+                    && self.get_time_in_running_state() >= self.config.session.break_duration()
+                {
+                    self.round += 1;
+                    self.reset_round();
+                }
+            }
+            RunningState::Paused => {}
+        }
+
+        std::mem::take(&mut self.events)
+    }
+
+    /// Stop the round's timer and everything in the arena until the game is resumed
+    pub fn pause(&mut self) -> Result<(), &'static str> {
+        match self.running_state {
+            RunningState::Paused => Err("The game is already paused"),
+            running_state if running_state.is_game_over() => Err("The round is already over"),
+            running_state => {
+                self.paused = Some((running_state, self.state_started_at));
+                self.clock.set_paused(true);
+                self.set_running_state(RunningState::Paused);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), &'static str> {
+        let (running_state, state_started_at) =
+            self.paused.take().ok_or("The game isn't paused")?;
+        self.clock.set_paused(false);
+        self.running_state = running_state;
+        self.state_started_at = state_started_at;
+        self.events
+            .push(GameEvent::RunningStateResumed(running_state));
+        Ok(())
+    }
+
+    /// Throw away the round being played and start it over from the splash screen, its scores
+    /// don't count. Once a round is over this starts the next one without waiting for the break.
+    pub fn restart(&mut self) {
+        if self.running_state.is_game_over() {
+            self.round += 1;
+        }
+        self.reset_round();
+    }
+
+    /// End the round now, the streamer wins since they are still standing
+    pub fn end_round(&mut self) -> Result<(), &'static str> {
+        if self.running_state.is_game_over() {
+            return Err("The round is already over");
+        }
+        self.paused = None;
+        self.clock.set_paused(false);
+        self.end_game(RunningState::PlayerWon);
+        Ok(())
+    }
+
+    /// The scores saved from earlier rounds keyed by the chatters' user ids, for answering
+    /// chatters asking about the leaderboard
+    pub fn set_high_scores(&mut self, high_scores: HashMap<String, Score>) {
+        self.leaderboard.set_high_scores(high_scores);
+    }
+
+    /// The chatters that joined during the splash screen, in the order they joined
+    pub fn get_lobby_players(&self) -> &[Chatter] {
+        self.lobby.get_players()
+    }
+
+    /// How many more chatters have to join before the round can start
+    pub fn get_players_needed(&self) -> usize {
+        (self.config.round.min_players as usize).saturating_sub(self.lobby.get_players().len())
+    }
+
+    /// How long until the round starts if enough players have joined by then,
+    /// `None` when the round isn't about to start
+    pub fn get_time_until_round_starts(&self) -> Option<Duration> {
+        if self.running_state != RunningState::StartingSoon {
+            return None;
+        }
+        Some(
+            self.config
+                .round
+                .splash_duration()
+                .checked_sub(self.get_time_in_running_state())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Which round of the session this is, counting from 1
+    pub fn get_round(&self) -> u32 {
+        self.round
+    }
+
+    /// The last round of the session is over and no more rounds will start
+    pub fn is_session_over(&self) -> bool {
+        let rounds = self.config.session.rounds;
+        rounds > 0 && self.round >= rounds && self.running_state.is_game_over()
+    }
+
+    /// The scores of every finished round this session keyed by the chatters' user ids
+    pub fn get_session_scores(&self) -> &HashMap<String, Score> {
+        &self.session_scores
+    }
+
+    pub fn get_running_state(&self) -> RunningState {
+        self.running_state
+    }
+
+    pub fn get_game_objects(&self) -> &[GameObject] {
+        &self.game_objects
+    }
+
+    /// This round's scores keyed by the chatters' user ids
+    pub fn get_scores(&self) -> &HashMap<String, Score> {
+        &self.scores
+    }
+
+    /// The drops that are about to land, for warning the streamer
+    pub fn get_incoming_drops(&self) -> &[IncomingDrop] {
+        &self.incoming_drops
+    }
+
+    /// The drops that are warning or waiting in the queue, in the order they will land,
+    /// with where they will land as far as that is known yet
+    pub fn get_upcoming_drops(&self) -> Vec<(&Command, DropTarget)> {
+        self.incoming_drops
+            .iter()
+            .map(|incoming_drop| {
+                let column = DropTarget::Column(incoming_drop.column);
+                (&incoming_drop.command, column)
+            })
+            .chain(
+                self.command_queue
+                    .iter()
+                    .map(|command| (command, command.target)),
+            )
+            .collect()
+    }
+
+    pub fn get_recent_hits(&self) -> &VecDeque<Chatter> {
+        &self.recent_hits
+    }
+
+    pub fn get_sprites(&self) -> Option<&Sprites> {
+        self.sprites.as_ref()
+    }
+
+    pub fn get_command_queue_metrics(&self) -> &CommandQueueMetrics {
+        self.command_queue.get_metrics()
+    }
+
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The random number generator for anything outside of the simulation that still needs
+    /// to be reproducible, like the message picked for the credits
+    pub fn get_rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// How many times the simulation has been updated, which keeps counting while the game is paused
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn get_clock(&self) -> &GameClock {
+        &self.clock
+    }
+
+    /// Run the game slower or faster than real time, for example 2.0 for turbo mode.
+    /// The finale is slowed down on top of this.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale;
+    }
+
+    /// How long the simulation has been running for in game time, which stands still while paused
+    pub fn get_time_since_start(&self) -> Duration {
+        self.clock.get_elapsed()
+    }
+
+    pub fn get_player_lives_left(&self) -> u8 {
+        if let Some(player) = self.get_player() {
+            player.get_lives_left().unwrap_or(self.config.round.lives)
+        } else {
+            0
+        }
+    }
+
+    /// Take in an index like 3
+    /// which should return the middle x,y coordinates of the corresponding drop zone
+    pub fn get_column_coordinates_by_index(&self, index: u8) -> Point2<f32> {
+        let single_drop_zone_width = self.arena_size.0 / self.config.world.drop_zone_count as f32;
+        Point2::new(
+            index as f32 * single_drop_zone_width + single_drop_zone_width / 2.0,
+            DROP_ZONE_HEIGHT / 2.0,
+        )
+    }
+
+    pub fn get_highest_scorer(&self) -> Option<(String, u128)> {
+        self.scores
+            .values()
+            .max_by(|a, b| a.points.cmp(&b.points))
+            .map(|score| (score.name.to_owned(), score.points))
+    }
+
+    fn update_round(&mut self, input: &PlayerInput) {
+        if self.get_time_in_running_state() >= self.config.round.game_time() {
+            self.end_game(RunningState::PlayerWon);
+            return;
+        }
+
+        for command in self
+            .command_queue
+            .take_ready(self.clock.get_ticks(), &self.config)
+        {
+            let column = self.get_target_column(&command);
+            let warning = self.config.drops.warning();
+            if warning > Duration::default() {
+                let incoming_drop = IncomingDrop::new(command, column, &self.clock, warning);
+                self.incoming_drops.push(incoming_drop);
+            } else {
+                self.handle_command(command, column);
+            }
+        }
+
+        let clock = &self.clock;
+        let (landed, incoming_drops) = std::mem::take(&mut self.incoming_drops)
+            .into_iter()
+            .partition(|incoming_drop| incoming_drop.has_landed(clock));
+        self.incoming_drops = incoming_drops;
+        for incoming_drop in landed {
+            self.handle_command(incoming_drop.command, incoming_drop.column);
+        }
+
+        let clock = &self.clock;
+        let arena_size = self.arena_size;
+        let gravity_force = self.config.world.gravity_force;
+        let collidable_game_objects: Vec<GameObject> = self
+            .game_objects
+            .clone()
+            .into_iter()
+            .filter(|game_object| game_object.collidable)
+            .collect();
+
+        self.game_objects.iter_mut().for_each(|game_object| {
+            if let Err(error) = game_object.update(
+                clock,
+                arena_size,
+                gravity_force,
+                input,
+                &collidable_game_objects,
+            ) {
+                eprintln!("error running update: {}", error)
+            }
+        });
+
+        self.game_objects
+            .retain(|game_object| game_object.is_alive());
+
+        self.handle_physics_events();
+
+        if self.get_player().is_none() {
+            self.end_game(RunningState::ChatWon);
+        }
+    }
+
+    fn get_target_column(&self, command: &Command) -> u8 {
+        command
+            .target
+            .get_column(self.get_player_column(), self.config.world.drop_zone_count)
+    }
+
+    fn handle_command(&mut self, command: Command, column: u8) {
+        let chatter = command.chatter.clone();
+        let drop_zone_location = self.get_column_coordinates_by_index(column);
+        self.game_objects.push(command.handle(
+            drop_zone_location,
+            self.sprites.as_ref(),
+            &mut self.rng,
+        ));
+        self.add_points(&chatter, POINTS_FOR_DROP);
+        self.events.push(GameEvent::ObjectDropped(chatter));
+    }
+
+    fn handle_control_command(&mut self, command: ControlCommand, chatter: &Chatter) {
+        let result = match command {
+            ControlCommand::Pause => self
+                .pause()
+                .map(|()| format!("{} paused the game", chatter.name)),
+            ControlCommand::Resume => self
+                .resume()
+                .map(|()| format!("{} resumed the game", chatter.name)),
+            ControlCommand::Restart => {
+                self.restart();
+                Ok(format!("{} restarted the round", chatter.name))
+            }
+            ControlCommand::End => self
+                .end_round()
+                .map(|()| format!("{} ended the round", chatter.name)),
+            ControlCommand::Ban(name) => match self.seen_chatters.get(&name) {
+                Some(user_id) => {
+                    self.banned.insert(user_id.clone());
+                    Ok(format!("{} can't play any more", name))
+                }
+                None => {
+                    let message = format!("{} hasn't chatted yet, so they can't be banned", name);
+                    return self.events.push(GameEvent::SendToChat(message));
+                }
+            },
+        };
+        let message = result.unwrap_or_else(|error| error.to_owned());
+        self.events.push(GameEvent::SendToChat(message));
+    }
+
+    /// The drops sent during the splash screen land first, held to the same limits as the
+    /// drops sent during the round
+    fn start_playing(&mut self) {
+        self.send_game_started_message();
+        self.set_running_state(RunningState::Playing);
+        for commands in self.lobby.take_first_drops() {
+            if let Some(chatter) = commands.first().map(|command| command.chatter.clone()) {
+                self.queue_drops(&chatter, commands, true);
+            }
+        }
+    }
+
+    /// A fresh arena for the next round, sent back to the splash screen
+    fn reset_round(&mut self) {
+        let (send_event, receive_event) = channel();
+        self.game_objects = vec![Self::create_player(
+            self.sprites.as_ref(),
+            &self.config,
+            send_event,
+        )];
+        self.receive_event = receive_event;
+        self.scores.clear();
+        self.drop_limiter = DropLimiter::default();
+        self.command_queue.clear();
+        self.incoming_drops.clear();
+        self.recent_hits.clear();
+        self.lobby.take_first_drops();
+        self.paused = None;
+        self.clock.set_paused(false);
+        self.set_running_state(RunningState::StartingSoon);
+        let message = Self::get_starting_soon_message(&self.config);
+        self.events.push(GameEvent::SendToChat(message));
+    }
+
+    /// Keeps the name the chatter was last seen with in case they changed it during the round
+    fn add_points(&mut self, chatter: &Chatter, points: u128) {
+        let score = self
+            .scores
+            .entry(chatter.user_id.clone())
+            .or_insert_with(|| Score::new(chatter.name.clone(), 0));
+        score.name = chatter.name.clone();
+        score.points += points;
+    }
+
+    /// Physics systems report what happened to them through a channel, pass those along
+    /// and score any hits on the player
+    fn handle_physics_events(&mut self) {
+        let physics_events: Vec<GameEvent> = self.receive_event.try_iter().collect();
+        for event in physics_events {
+            if let GameEvent::PlayerHit(chatter) = &event {
+                let message_to_chat =
+                    format!("Hit! {} gets {} points", &chatter.name, POINTS_FOR_HIT);
+                self.add_points(chatter, POINTS_FOR_HIT);
+                self.recent_hits.push_front(chatter.clone());
+                self.recent_hits.truncate(RECENT_HITS_KEPT);
+                self.events.push(event.clone());
+                self.events.push(GameEvent::SendToChat(message_to_chat));
+            } else {
+                self.events.push(event);
+            }
+        }
+    }
+
+    fn get_player(&self) -> Option<&GameObject> {
+        self.game_objects
+            .iter()
+            .find(|game_object| game_object.my_type == GameObjectType::Player)
+    }
+
+    /// The column the middle of the streamer is in
+    fn get_player_column(&self) -> u8 {
+        let drop_zone_count = self.config.world.drop_zone_count;
+        match self.get_player() {
+            Some(player) => {
+                let single_drop_zone_width = self.arena_size.0 / drop_zone_count as f32;
+                let player_x = player.location.x + player.location.w / 2.0;
+                ((player_x / single_drop_zone_width).max(0.0) as u8).min(drop_zone_count - 1)
+            }
+            None => drop_zone_count / 2,
+        }
+    }
+
+    fn create_player(
+        sprites: Option<&Sprites>,
+        config: &Config,
+        send_event: Sender<GameEvent>,
+    ) -> GameObject {
+        let player_draw_system = sprites.map(|sprites| {
+            PlayerDrawSystem::new(
+                sprites.player_left.clone(),
+                sprites.player_forward.clone(),
+                PLAYER_SIZE,
+            )
+        });
+        let player_physics_system = PlayerPhysics::new(send_event, &config.player);
+
+        GameObject::new(
+            250.0,
+            250.0,
+            player_draw_system.map(|draw_system| Box::new(draw_system) as Box<dyn DrawSystem>),
+            PLAYER_SIZE.0,
+            PLAYER_SIZE.1,
+            Some(Box::new(player_physics_system)),
+            true,
+            None,
+            GameObjectType::Player,
+            Some(Box::new(PlayerLifeSystem::new(
+                config.round.lives,
+                &config.player,
+            ))),
+        )
+    }
+
+    fn set_running_state(&mut self, running_state: RunningState) {
+        self.running_state = running_state;
+        self.state_started_at = self.clock.get_elapsed();
+        self.events
+            .push(GameEvent::RunningStateChanged(running_state));
+    }
+
+    /// The last seconds of the round play out in slow motion when the config asks for it
+    fn is_in_finale(&self) -> bool {
+        let finale = self.config.round.finale();
+        self.running_state == RunningState::Playing
+            && finale > Duration::default()
+            && self.get_time_in_running_state() + finale >= self.config.round.game_time()
+    }
+
+    fn get_time_in_running_state(&self) -> Duration {
+        self.clock.get_time_since(self.state_started_at)
+    }
+
+    fn get_starting_soon_message(config: &Config) -> String {
+        format!("In {} seconds the Get the Streamer game will begin, you can play through chat with the commands on the right side of the game. Type #join or send your first drop now to be ready.", config.round.splash_duration_seconds)
+    }
+
+    fn send_game_started_message(&mut self) {
+        let message = format!(
+            "You have {} seconds to send your commands to Get the Streamer!",
+            self.config.round.game_time_seconds
+        );
+        self.events.push(GameEvent::SendToChat(message));
+    }
+
+    fn send_game_ended_message(&mut self, winner: RunningState) {
+        let (highest_scorer, score) = self
+            .get_highest_scorer()
+            .unwrap_or_else(|| ("nobody".to_owned(), 0));
+
+        let message = match winner {
+            RunningState::ChatWon => format!(
+                "You all won, highest scorer was {} with {} points!",
+                highest_scorer, score
+            ),
+            _ => format!(
+                "The Streamer won the game despite the best efforts of {} who got {} points!",
+                highest_scorer, score
+            ),
+        };
+
+        self.events.push(GameEvent::SendToChat(message));
+    }
+
+    fn end_game(&mut self, new_running_state: RunningState) {
+        self.command_queue.clear();
+        self.incoming_drops.clear();
+        for (user_id, score) in &self.scores {
+            let session_score = self
+                .session_scores
+                .entry(user_id.to_owned())
+                .or_insert_with(|| Score::new(score.name.clone(), 0));
+            session_score.name = score.name.clone();
+            session_score.points += score.points;
+        }
+        self.send_game_ended_message(new_running_state);
+        self.set_running_state(new_running_state);
+        self.send_session_message();
+    }
+
+    fn send_session_message(&mut self) {
+        if self.round > 1 {
+            if let Some(leader) = self
+                .session_scores
+                .values()
+                .max_by(|a, b| a.points.cmp(&b.points))
+            {
+                let message = format!(
+                    "After {} rounds {} leads the session with {} points",
+                    self.round, leader.name, leader.points
+                );
+                self.events.push(GameEvent::SendToChat(message));
+            }
+        }
+
+        let message = if self.is_session_over() {
+            "That was the last round, thanks for playing!".to_owned()
+        } else {
+            format!(
+                "The next round starts in {} seconds",
+                self.config.session.break_seconds
+            )
+        };
+        self.events.push(GameEvent::SendToChat(message));
+    }
+}
+
+/// Like "1 more player" or "3 more players"
+pub fn describe_players(count: usize) -> String {
+    if count == 1 {
+        "1 more player".to_owned()
+    } else {
+        format!("{} more players", count)
+    }
+}
@@ -1,40 +1,54 @@
-use std::time::{Duration, Instant};
-
 use ggez::{
     graphics::{self, DrawParam, Font, Scale, Text},
     nalgebra::Point2,
     Context, GameResult,
 };
 
+const DETAILS_FONT_SIZE: f32 = 40.0;
+const DETAILS_SPACING: f32 = 15.0;
+
 pub struct Splash {
     text: Text,
     location: Point2<f32>,
-    finished_at: Instant,
+    arena_width: f32,
 }
 
 impl Splash {
-    pub fn new(arena_size: (f32, f32), context: &mut Context, duration: Duration) -> Self {
-        let mut text = Text::new("Starting Soon");
+    pub fn new(message: &str, arena_size: (f32, f32), context: &mut Context) -> Self {
+        let mut text = Text::new(message);
         text.set_font(Font::default(), Scale::uniform(100.0));
         let text_size = text.dimensions(context);
         let location = Point2::new(
             arena_size.0 / 2.0 - text_size.0 as f32 / 2.0,
             arena_size.1 / 2.0 - text_size.1 as f32 / 2.0,
         );
-        let finished_at = Instant::now() + duration;
 
         Self {
             text,
             location,
-            finished_at,
+            arena_width: arena_size.0,
         }
     }
 
-    pub fn draw(&self, context: &mut Context) -> GameResult<()> {
-        graphics::draw(context, &self.text, DrawParam::new().dest(self.location))
-    }
+    /// The details are lines of smaller text under the message, like a countdown
+    pub fn draw(&self, context: &mut Context, details: &[String]) -> GameResult<()> {
+        graphics::draw(context, &self.text, DrawParam::new().dest(self.location))?;
 
-    pub fn is_done(&self) -> bool {
-        Instant::now() >= self.finished_at
+        let mut detail_y = self.location.y + self.text.height(context) as f32 + DETAILS_SPACING;
+        for detail in details {
+            let mut text = Text::new(detail.as_str());
+            text.set_font(Font::default(), Scale::uniform(DETAILS_FONT_SIZE));
+            let (width, height) = text.dimensions(context);
+            graphics::draw(
+                context,
+                &text,
+                DrawParam::new().dest(Point2::new(
+                    self.arena_width / 2.0 - width as f32 / 2.0,
+                    detail_y,
+                )),
+            )?;
+            detail_y += height as f32 + DETAILS_SPACING;
+        }
+        Ok(())
     }
 }
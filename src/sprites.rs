@@ -2,9 +2,12 @@ use ggez::graphics::{DrawParam, Image, Rect};
 use ggez::nalgebra::Point2;
 use ggez::{graphics, Context, GameResult};
 use graphics::Color;
+use std::collections::HashMap;
 use std::time::Duration;
 
-#[derive(Debug)]
+use crate::object_registry::ObjectRegistry;
+
+#[derive(Debug, Clone)]
 pub struct Sprite {
     image: Image,
     individual_sprite_rects: Vec<Rect>,
@@ -18,7 +21,7 @@ pub struct Sprite {
 impl Sprite {
     pub fn new(
         context: &mut Context,
-        path: &'static str,
+        path: &str,
         sprites_accross: u16,
         sprites_down: u16,
     ) -> GameResult<Sprite> {
@@ -98,3 +101,38 @@ impl Sprite {
         }
     }
 }
+
+/// Every sprite sheet used by game objects, loaded once up front so that
+/// objects can be created later on without needing a `Context`
+#[derive(Debug, Clone)]
+pub struct Sprites {
+    pub player_forward: Sprite,
+    pub player_left: Sprite,
+    /// by the name of the object in the registry
+    objects: HashMap<String, Sprite>,
+}
+
+impl Sprites {
+    pub fn new(context: &mut Context, objects: &ObjectRegistry) -> GameResult<Sprites> {
+        let mut object_sprites = HashMap::new();
+        for object in objects.get_objects() {
+            let sprite = Sprite::new(
+                context,
+                &object.sprite,
+                object.sprite_columns,
+                object.sprite_rows,
+            )?;
+            object_sprites.insert(object.name.clone(), sprite);
+        }
+
+        Ok(Sprites {
+            player_forward: Sprite::new(context, "/player_forward.png", 8, 1)?,
+            player_left: Sprite::new(context, "/player_left.png", 8, 1)?,
+            objects: object_sprites,
+        })
+    }
+
+    pub fn get_object(&self, name: &str) -> Option<&Sprite> {
+        self.objects.get(name)
+    }
+}
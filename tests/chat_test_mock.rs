@@ -1,13 +1,11 @@
+use get_the_streamer_game::{Chatter, FakeIrcServer};
 use rand::Rng;
-use std::sync::mpsc::Sender;
 use std::thread;
 use std::time::Duration;
-use twitch_chat_wrapper::chat_message::ChatMessage;
-use twitch_chat_wrapper::twitchchat::messages::Privmsg;
-use twitch_chat_wrapper::twitchchat::FromIrcMessage;
 
+/// Simulates a busy chat full of bots sending commands through the fake IRC server
 pub fn run(
-    send_incoming_chat_message: Sender<ChatMessage>,
+    server: FakeIrcServer,
     num_bot_users: u64,
     initial_commands_delay: Duration,
     min_command_interval_ms: u64,
@@ -25,26 +23,20 @@ pub fn run(
                 v
             });
 
-        let mock_user_names: Vec<String> = (1..num_bot_users)
-            .map(|u| format!("test_bot{}", u))
+        let mock_users: Vec<Chatter> = (1..num_bot_users)
+            .map(|u| Chatter::new(format!("test_bot{}", u), (0, 0, 0), false))
             .collect();
         let mut rng = rand::thread_rng();
 
         thread::sleep(initial_commands_delay);
 
         loop {
-            let input = format!(
-                ":{mock_user}!user@host PRIVMSG #mock_channel :#{command} {column}\r\n",
-                mock_user = mock_user_names[rng.gen_range(0, mock_user_names.len())],
+            let message = format!(
+                "#{command} {column}",
                 command = command_pool[rng.gen_range(0, command_pool.len())],
                 column = rng.gen_range(0, 10)
             );
-            let (_pos, msg_a) = twitch_chat_wrapper::twitchchat::irc::parse_one(&input).unwrap();
-
-            let test_msg: Privmsg = Privmsg::from_irc(msg_a).unwrap();
-            send_incoming_chat_message
-                .send(ChatMessage::new(test_msg))
-                .unwrap();
+            server.send_chat_message(&mock_users[rng.gen_range(0, mock_users.len())], &message);
 
             thread::sleep(Duration::from_millis(
                 rng.gen_range(min_command_interval_ms, max_command_interval_ms),
@@ -0,0 +1,64 @@
+use get_the_streamer_game::{Config, GameObjectType, Harness};
+
+fn count_enemies_after(harness: &mut Harness, message: &str) -> usize {
+    harness.chat("alice", message);
+    harness.run_ticks(5);
+    harness.count_game_objects(GameObjectType::Enemy)
+}
+
+#[test]
+fn commands_ignore_capitals_and_extra_spaces() {
+    let mut harness = Harness::started(Config::default(), 1);
+
+    assert_eq!(count_enemies_after(&mut harness, "  #Fire   3 "), 1);
+    assert_eq!(harness.get_score("alice"), 1);
+}
+
+#[test]
+fn one_message_can_drop_several_objects() {
+    let mut harness = Harness::started(Config::default(), 1);
+
+    assert_eq!(count_enemies_after(&mut harness, "#fire 3 #sword 7"), 2);
+    assert_eq!(harness.get_score("alice"), 2);
+}
+
+#[test]
+fn objects_can_be_dropped_into_ranges_and_lists_of_columns() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    let mut harness = Harness::started(config, 1);
+
+    assert_eq!(count_enemies_after(&mut harness, "#fire 2-4,7"), 4);
+}
+
+#[test]
+fn drops_past_the_chatters_limit_are_left_out() {
+    let mut config = Config::default();
+    config.drops.per_round = 2;
+    let mut harness = Harness::started(config, 1);
+
+    assert_eq!(count_enemies_after(&mut harness, "#fire 0-9"), 2);
+}
+
+#[test]
+fn typos_get_a_suggestion() {
+    let mut harness = Harness::started(Config::default(), 1);
+
+    assert_eq!(count_enemies_after(&mut harness, "#snaek 3"), 0);
+    assert_eq!(count_enemies_after(&mut harness, "#hello everyone"), 0);
+    assert_eq!(harness.get_chat_log().last(), Some(&"Did you mean #snake?"));
+}
+
+#[test]
+fn good_drops_land_even_when_others_in_the_message_dont_make_sense() {
+    let mut harness = Harness::started(Config::default(), 1);
+
+    assert_eq!(
+        count_enemies_after(&mut harness, "#fire 3 #snaek 5 #sword 42"),
+        1
+    );
+    assert_eq!(
+        harness.get_chat_log().last(),
+        Some(&"Did you mean #snake? The given column is outside of the arena")
+    );
+}
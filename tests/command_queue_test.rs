@@ -0,0 +1,90 @@
+use get_the_streamer_game::{Config, GameObjectType, Harness, OverflowPolicy};
+
+/// Everyone in a raid drops a fire in a different column in the same frame
+fn raid(harness: &mut Harness, chatters: u8) {
+    for index in 0..chatters {
+        harness.chat(
+            &format!("raider{}", index),
+            &format!("#fire {}", index % 10),
+        );
+    }
+}
+
+#[test]
+fn drops_land_a_few_at_a_time() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    config.queue.max_spawns_per_tick = 2;
+    let mut harness = Harness::started(config, 1);
+    raid(&mut harness, 5);
+    assert_eq!(
+        harness.get_simulation().get_command_queue_metrics().depth,
+        5
+    );
+
+    harness.run_ticks(1);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 2);
+    harness.run_ticks(2);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 5);
+
+    let metrics = harness.get_simulation().get_command_queue_metrics();
+    assert_eq!(metrics.depth, 0);
+    assert_eq!(metrics.max_depth, 5);
+    assert_eq!(metrics.spawned, 5);
+    assert_eq!(metrics.max_wait_ticks, 3);
+}
+
+#[test]
+fn drops_that_would_land_late_are_thrown_out() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    config.queue.max_spawns_per_tick = 1;
+    config.queue.max_length = 100;
+    config.queue.max_wait_seconds = 0.5;
+    let mut harness = Harness::started(config, 1);
+    raid(&mut harness, 40);
+    harness.run_ticks(60);
+
+    let metrics = harness.get_simulation().get_command_queue_metrics();
+    assert_eq!(metrics.spawned, 30);
+    assert_eq!(metrics.expired, 10);
+    assert_eq!(metrics.depth, 0);
+}
+
+#[test]
+fn full_queues_drop_the_oldest() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    config.queue.max_length = 3;
+    config.queue.overflow = OverflowPolicy::DropOldest;
+    let mut harness = Harness::started(config, 1);
+    raid(&mut harness, 5);
+    harness.run_ticks(5);
+
+    assert_eq!(harness.get_score("raider0"), 0);
+    assert_eq!(harness.get_score("raider1"), 0);
+    assert_eq!(harness.get_score("raider4"), 1);
+    let metrics = harness.get_simulation().get_command_queue_metrics();
+    assert_eq!(metrics.dropped, 2);
+    assert_eq!(metrics.spawned, 3);
+}
+
+#[test]
+fn full_queues_merge_duplicate_drops() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    config.queue.max_length = 2;
+    config.queue.overflow = OverflowPolicy::MergeDuplicates;
+    let mut harness = Harness::started(config, 1);
+    harness.chat("alice", "#fire 3");
+    harness.chat("bob", "#fire 5");
+    harness.chat("carol", "#fire 3");
+    harness.run_ticks(1);
+
+    assert_eq!(harness.get_score("alice"), 1);
+    assert_eq!(harness.get_score("bob"), 1);
+    assert_eq!(harness.get_score("carol"), 0);
+    let metrics = harness.get_simulation().get_command_queue_metrics();
+    assert_eq!(metrics.merged, 1);
+    assert_eq!(metrics.dropped, 0);
+}
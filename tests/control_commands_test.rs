@@ -0,0 +1,101 @@
+use get_the_streamer_game::{Chatter, ChatterRole, Config, GameObjectType, Harness, RunningState};
+
+fn moderator() -> Chatter {
+    Chatter::new("Mod".to_owned(), (0, 0, 0), false).with_role(ChatterRole::Moderator)
+}
+
+#[test]
+fn pausing_stops_the_round_timer() {
+    let mut harness = Harness::started(Config::default(), 1);
+    harness.run_ticks(60 * 100);
+    harness.chat_as(moderator(), "!gts pause");
+    harness.run_ticks(60 * 30);
+    assert_eq!(harness.get_running_state(), RunningState::Paused);
+
+    harness.chat("alice", "#fire 5");
+    harness.run_ticks(1);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 0);
+
+    harness.chat_as(moderator(), "!gts resume");
+    harness.run_ticks(60 * 10);
+    assert_eq!(harness.get_running_state(), RunningState::Playing);
+    harness.run_ticks(60 * 10);
+    assert_eq!(harness.get_running_state(), RunningState::PlayerWon);
+    assert!(harness.get_chat_log().contains(&"Mod paused the game"));
+    assert!(harness.get_chat_log().contains(&"Mod resumed the game"));
+}
+
+#[test]
+fn viewers_cant_control_the_game() {
+    let mut harness = Harness::started(Config::default(), 1);
+    harness.chat("alice", "!gts end");
+    harness.chat("alice", "!gts pause");
+    harness.run_ticks(1);
+
+    assert_eq!(harness.get_running_state(), RunningState::Playing);
+}
+
+#[test]
+fn the_streamer_can_end_and_restart_the_round() {
+    let mut harness = Harness::started(Config::default(), 1);
+    let streamer =
+        Chatter::new("Streamer".to_owned(), (0, 0, 0), false).with_role(ChatterRole::Broadcaster);
+    harness.chat("alice", "#fire 5");
+    harness.run_ticks(1);
+    harness.chat_as(streamer.clone(), "!gts end");
+    assert_eq!(harness.get_running_state(), RunningState::PlayerWon);
+
+    harness.chat_as(streamer, "!gts restart");
+    assert_eq!(harness.get_running_state(), RunningState::StartingSoon);
+    assert_eq!(harness.get_score("alice"), 0);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 0);
+
+    harness.start_round();
+    assert_eq!(harness.get_player_lives_left(), 3);
+}
+
+#[test]
+fn banned_chatters_are_ignored() {
+    let mut harness = Harness::started(Config::default(), 1);
+    let alice = Chatter::new("alice".to_owned(), (0, 0, 0), false).with_user_id("1".to_owned());
+    harness.chat_as(alice, "hi");
+    harness.chat_as(moderator(), "!gts ban @Alice");
+    let renamed_alice =
+        Chatter::new("alice2".to_owned(), (0, 0, 0), false).with_user_id("1".to_owned());
+    harness.chat_as(renamed_alice, "#fire 5");
+    harness.chat("bob", "#fire 6");
+    harness.run_ticks(1);
+
+    assert_eq!(harness.get_score("alice2"), 0);
+    assert_eq!(harness.get_score("bob"), 1);
+    assert!(harness
+        .get_chat_log()
+        .contains(&"alice can't play any more"));
+
+    harness.chat_as(moderator(), "!gts ban carol");
+    harness.run_ticks(1);
+    assert!(harness
+        .get_chat_log()
+        .contains(&"carol hasn't chatted yet, so they can't be banned"));
+}
+
+#[test]
+fn drops_waiting_to_land_survive_a_pause() {
+    let mut config = Config::default();
+    config.queue.max_spawns_per_tick = 1;
+    let mut harness = Harness::started(config, 1);
+    harness.chat("alice", "#fire 3");
+    harness.chat("bob", "#fire 5");
+    harness.chat_as(moderator(), "!gts pause");
+    harness.run_ticks(60 * 10);
+
+    let clock = harness.get_simulation().get_clock();
+    assert!(clock.is_paused());
+    let paused_at = clock.get_ticks();
+    harness.run_ticks(1);
+    assert_eq!(harness.get_simulation().get_clock().get_ticks(), paused_at);
+
+    harness.chat_as(moderator(), "!gts resume");
+    harness.run_ticks(2);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 2);
+}
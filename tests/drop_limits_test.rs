@@ -0,0 +1,72 @@
+use get_the_streamer_game::{Config, Harness};
+
+fn count_messages(harness: &Harness, message: &str) -> usize {
+    harness
+        .get_chat_log()
+        .iter()
+        .filter(|chat_message| **chat_message == message)
+        .count()
+}
+
+#[test]
+fn spamming_chatters_are_told_to_wait_once() {
+    let mut harness = Harness::started(Config::default(), 1);
+    for column in 0..5 {
+        harness.chat("alice", &format!("#fire {}", column + 4));
+        harness.run_ticks(10);
+    }
+
+    assert_eq!(harness.get_score("alice"), 1);
+    assert_eq!(
+        count_messages(&harness, "alice can drop again in 2 seconds"),
+        1
+    );
+
+    harness.run_ticks(120);
+    harness.chat("alice", "#fire 9");
+    harness.run_ticks(1);
+    assert_eq!(harness.get_score("alice"), 2);
+}
+
+#[test]
+fn chatters_run_out_of_drops() {
+    let mut config = Config::default();
+    config.drops.cooldown_seconds = 0.0;
+    config.drops.per_round = 2;
+    let mut harness = Harness::started(config, 1);
+    for column in 5..9 {
+        harness.chat("alice", &format!("#fire {}", column));
+        harness.run_ticks(30);
+    }
+
+    assert_eq!(harness.get_score("alice"), 2);
+    assert_eq!(
+        count_messages(&harness, "alice has used all 2 of their drops this round"),
+        1
+    );
+}
+
+#[test]
+fn all_of_chat_can_only_drop_so_fast() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 2;
+    let mut harness = Harness::started(config, 1);
+    harness.chat("alice", "#fire 5");
+    harness.chat("bob", "#fire 6");
+    harness.chat("carol", "#fire 7");
+    harness.run_ticks(30);
+
+    assert_eq!(harness.get_score("carol"), 0);
+    assert_eq!(
+        count_messages(
+            &harness,
+            "Chat is dropping too fast, carol can drop again in 1 second"
+        ),
+        1
+    );
+
+    harness.run_ticks(30);
+    harness.chat("carol", "#fire 7");
+    harness.run_ticks(1);
+    assert_eq!(harness.get_score("carol"), 1);
+}
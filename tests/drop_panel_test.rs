@@ -0,0 +1,64 @@
+use get_the_streamer_game::{Chatter, Config, Harness};
+
+fn describe_upcoming_drops(harness: &Harness) -> Vec<String> {
+    harness
+        .get_simulation()
+        .get_upcoming_drops()
+        .iter()
+        .map(|(command, target)| {
+            format!(
+                "{} {} {}",
+                command.object.name, target, command.chatter.name
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn upcoming_drops_are_listed_in_the_order_they_land() {
+    let mut config = Config::default();
+    config.drops.warning_seconds = 1.0;
+    config.queue.max_spawns_per_tick = 1;
+    let mut harness = Harness::started(config, 1);
+    harness.chat("alice", "#fire 3");
+    harness.chat("bob", "#sword player");
+    harness.run_ticks(1);
+
+    assert_eq!(
+        describe_upcoming_drops(&harness),
+        vec!["fire 3 alice", "sword player bob"]
+    );
+
+    harness.run_ticks(1);
+    assert_eq!(describe_upcoming_drops(&harness).len(), 2);
+    assert!(!describe_upcoming_drops(&harness)[1].contains("player"));
+
+    harness.run_ticks(60);
+    assert!(describe_upcoming_drops(&harness).is_empty());
+}
+
+#[test]
+fn the_last_hits_are_remembered_newest_first() {
+    let mut harness = Harness::started(Config::default(), 1);
+    harness.chat("alice", "#fire player");
+    harness.run_ticks(120);
+
+    let recent_hits: Vec<&str> = harness
+        .get_simulation()
+        .get_recent_hits()
+        .iter()
+        .map(|chatter| chatter.name.as_str())
+        .collect();
+    assert_eq!(recent_hits, vec!["alice"]);
+}
+
+#[test]
+fn dark_names_are_lightened_so_they_can_be_read() {
+    let navy = Chatter::new("alice".to_owned(), (0, 0, 60), false).get_readable_color();
+    let brightness = 0.299 * navy.r + 0.587 * navy.g + 0.114 * navy.b;
+    assert!((brightness - 0.5).abs() < 0.01);
+    assert!(navy.b > navy.r);
+
+    let yellow = Chatter::new("bob".to_owned(), (255, 255, 0), false);
+    assert_eq!(yellow.get_readable_color(), yellow.get_color());
+}
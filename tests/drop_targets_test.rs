@@ -0,0 +1,106 @@
+use get_the_streamer_game::{Config, GameObject, GameObjectType, Harness, PlayerInput};
+
+fn get_column(harness: &Harness, game_object: &GameObject) -> u8 {
+    let simulation = harness.get_simulation();
+    let column_width = simulation.get_column_coordinates_by_index(1).x
+        - simulation.get_column_coordinates_by_index(0).x;
+    ((game_object.location.x + game_object.location.w / 2.0) / column_width) as u8
+}
+
+fn get_player_column(harness: &Harness) -> u8 {
+    let player = harness
+        .get_game_objects()
+        .iter()
+        .find(|game_object| game_object.my_type == GameObjectType::Player)
+        .unwrap();
+    get_column(harness, player)
+}
+
+/// The column the object dropped with the message landed in
+fn drop_into(harness: &mut Harness, name: &str, target: &str) -> u8 {
+    harness.chat(name, &format!("#fire {}", target));
+    harness.run_ticks(1);
+    let fire = harness
+        .get_game_objects()
+        .iter()
+        .rev()
+        .find(|game_object| game_object.my_type == GameObjectType::Enemy)
+        .unwrap();
+    get_column(harness, fire)
+}
+
+#[test]
+fn named_targets_are_worked_out_from_where_the_streamer_is() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    let mut harness = Harness::started(config, 1);
+    harness.set_input(PlayerInput {
+        right: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(40);
+    harness.set_input(PlayerInput::default());
+    let player_column = get_player_column(&harness);
+    assert!((2..5).contains(&player_column));
+
+    assert_eq!(drop_into(&mut harness, "alice", "player"), player_column);
+    assert_eq!(drop_into(&mut harness, "bob", "LEFT"), player_column - 1);
+    assert_eq!(drop_into(&mut harness, "carol", "right"), player_column + 1);
+    assert_eq!(drop_into(&mut harness, "dave", "far"), 9);
+    assert_eq!(drop_into(&mut harness, "erin", "center"), 5);
+}
+
+#[test]
+fn left_and_right_next_to_a_wall_drop_on_the_other_side() {
+    let mut config = Config::default();
+    config.drops.max_per_second = 0;
+    let mut harness = Harness::started(config, 1);
+    harness.set_input(PlayerInput {
+        left: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(60);
+    assert_eq!(get_player_column(&harness), 0);
+    assert_eq!(drop_into(&mut harness, "alice", "left"), 1);
+    assert_eq!(drop_into(&mut harness, "bob", "right"), 1);
+
+    harness.set_input(PlayerInput {
+        right: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(60 * 3);
+    assert_eq!(get_player_column(&harness), 9);
+    assert_eq!(drop_into(&mut harness, "carol", "right"), 8);
+    assert_eq!(drop_into(&mut harness, "dave", "left"), 8);
+}
+
+#[test]
+fn targets_follow_the_streamer_until_the_drop_lands() {
+    let mut config = Config::default();
+    config.drops.cooldown_seconds = 0.0;
+    config.drops.max_per_second = 0;
+    config.queue.max_spawns_per_tick = 1;
+    config.queue.max_length = 50;
+    let mut harness = Harness::started(config, 1);
+    for name in &["alice", "bob", "carol"] {
+        harness.chat(name, "#sword 0-9");
+    }
+    harness.chat("dave", "#fire player");
+    let column_when_sent = get_player_column(&harness);
+    harness.set_input(PlayerInput {
+        right: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(31);
+
+    let fire = harness
+        .get_game_objects()
+        .iter()
+        .find(|game_object| {
+            game_object.my_type == GameObjectType::Enemy
+                && game_object.chatter.as_ref().unwrap().name == "dave"
+        })
+        .unwrap();
+    assert_ne!(get_column(&harness, fire), column_when_sent);
+    assert_eq!(get_column(&harness, fire), get_player_column(&harness));
+}
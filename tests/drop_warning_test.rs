@@ -0,0 +1,57 @@
+use get_the_streamer_game::{Config, GameObjectType, Harness, PlayerInput};
+
+fn create_config() -> Config {
+    let mut config = Config::default();
+    config.drops.warning_seconds = 1.0;
+    config
+}
+
+fn get_incoming_columns(harness: &Harness) -> Vec<u8> {
+    harness
+        .get_simulation()
+        .get_incoming_drops()
+        .iter()
+        .map(|incoming_drop| incoming_drop.column)
+        .collect()
+}
+
+#[test]
+fn drops_land_after_the_warning() {
+    let mut harness = Harness::started(create_config(), 1);
+    harness.chat("alice", "#fire 3");
+    harness.run_ticks(1);
+
+    assert_eq!(get_incoming_columns(&harness), vec![3]);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 0);
+    assert_eq!(harness.get_score("alice"), 0);
+
+    harness.run_ticks(60);
+    assert!(get_incoming_columns(&harness).is_empty());
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 1);
+    assert_eq!(harness.get_score("alice"), 1);
+}
+
+#[test]
+fn drops_land_where_the_warning_was_shown() {
+    let mut harness = Harness::started(create_config(), 1);
+    harness.chat("alice", "#fire player");
+    harness.run_ticks(1);
+    let warned_column = get_incoming_columns(&harness)[0];
+
+    harness.set_input(PlayerInput {
+        right: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(60);
+
+    let fire = harness
+        .get_game_objects()
+        .iter()
+        .find(|game_object| game_object.my_type == GameObjectType::Enemy)
+        .unwrap();
+    let warned_x = harness
+        .get_simulation()
+        .get_column_coordinates_by_index(warned_column)
+        .x;
+    assert_eq!(fire.location.x + fire.location.w / 2.0, warned_x);
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use get_the_streamer_game::{Config, FixedTimestep, GameObjectType, Harness, PlayerInput};
+
+fn get_location(harness: &Harness, game_object_type: GameObjectType) -> (f32, f32) {
+    let game_object = harness
+        .get_game_objects()
+        .iter()
+        .find(|game_object| game_object.my_type == game_object_type)
+        .unwrap();
+    (game_object.location.x, game_object.location.y)
+}
+
+/// Where the fire and the streamer are after half a second of holding right
+fn play_half_a_second(framerate_target: u32) -> ((f32, f32), (f32, f32)) {
+    let mut config = Config::default();
+    config.world.framerate_target = framerate_target;
+    let mut harness = Harness::started(config, 1);
+    harness.chat("alice", "#fire 5");
+    harness.set_input(PlayerInput {
+        right: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(framerate_target / 2);
+
+    (
+        get_location(&harness, GameObjectType::Enemy),
+        get_location(&harness, GameObjectType::Player),
+    )
+}
+
+/// How many lives the streamer loses to two fires dropped on them a second apart, which is
+/// sooner than they can be hit again
+fn count_hits_from_two_fires(framerate_target: u32) -> u8 {
+    let mut config = Config::default();
+    config.world.framerate_target = framerate_target;
+    Harness::started(config, 1).count_lives_lost_to_two_fires(framerate_target)
+}
+
+fn assert_close((x, y): (f32, f32), (other_x, other_y): (f32, f32)) {
+    assert!(
+        (x - other_x).abs() < 5.0,
+        "{} and {} are too far apart",
+        x,
+        other_x
+    );
+    assert!(
+        (y - other_y).abs() < 5.0,
+        "{} and {} are too far apart",
+        y,
+        other_y
+    );
+}
+
+#[test]
+fn objects_move_the_same_at_any_framerate() {
+    let (fire, player) = play_half_a_second(60);
+    let (fast_fire, fast_player) = play_half_a_second(144);
+
+    assert_close(fire, fast_fire);
+    assert_close(player, fast_player);
+    assert_eq!(count_hits_from_two_fires(60), 1);
+    assert_eq!(count_hits_from_two_fires(144), 1);
+}
+
+#[test]
+fn slow_frames_catch_up_on_missed_updates() {
+    let mut fixed_timestep = FixedTimestep::new(60, 5);
+    fixed_timestep.add_frame_time(Duration::from_millis(55));
+    let mut updates = 0;
+    while fixed_timestep.take_step() {
+        updates += 1;
+    }
+    assert_eq!(updates, 3);
+    assert!(fixed_timestep.get_alpha() > 0.0 && fixed_timestep.get_alpha() < 1.0);
+
+    fixed_timestep.add_frame_time(Duration::from_secs(1));
+    let mut updates = 0;
+    while fixed_timestep.take_step() {
+        updates += 1;
+    }
+    assert_eq!(updates, 5);
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use get_the_streamer_game::{Config, GameClock, Harness};
+
+fn create_config() -> Config {
+    let mut config = Config::default();
+    config.round.game_time_seconds = 10;
+    config.round.splash_duration_seconds = 1;
+    config
+}
+
+/// How many lives the streamer loses to two fires dropped on them a second of game time apart
+fn count_hits_from_two_fires(time_scale: f32) -> u8 {
+    let mut config = create_config();
+    config.world.time_scale = time_scale;
+    let ticks_per_second = (60.0 / time_scale) as u32;
+    Harness::started(config, 1).count_lives_lost_to_two_fires(ticks_per_second)
+}
+
+fn count_round_ticks(config: Config) -> u32 {
+    let mut harness = Harness::started(config, 1);
+    let started_at = harness.get_simulation().get_ticks();
+    harness.finish_round();
+    harness.get_simulation().get_ticks() - started_at
+}
+
+#[test]
+fn the_clock_scales_and_stops_while_paused() {
+    let mut clock = GameClock::new(60);
+    for _ in 0..60 {
+        clock.tick();
+    }
+    assert_eq!(clock.get_elapsed(), Duration::from_secs(1));
+
+    clock.set_time_scale(0.5);
+    for _ in 0..60 {
+        clock.tick();
+    }
+    assert_eq!(clock.get_elapsed(), Duration::from_millis(1500));
+
+    clock.set_paused(true);
+    clock.tick();
+    assert_eq!(clock.get_delta(), Duration::default());
+    assert_eq!(clock.get_ticks(), 120);
+
+    clock.advance(Duration::from_millis(500));
+    assert_eq!(
+        clock.get_time_since(Duration::from_secs(1)),
+        Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn turbo_mode_ends_the_round_sooner() {
+    let normal_ticks = count_round_ticks(create_config());
+    let mut config = create_config();
+    config.world.time_scale = 2.0;
+    let turbo_ticks = count_round_ticks(config);
+
+    assert_eq!(normal_ticks, 60 * 10);
+    assert_eq!(turbo_ticks, 60 * 5);
+}
+
+#[test]
+fn a_slow_motion_finale_stretches_the_last_seconds() {
+    let mut config = create_config();
+    config.round.finale_seconds = 2.0;
+    config.round.finale_time_scale = 0.5;
+
+    assert_eq!(count_round_ticks(config), 60 * 8 + 60 * 4);
+}
+
+#[test]
+fn finales_that_are_not_a_length_of_time_are_rejected() {
+    assert!(Config::from_toml("[round]\nfinale_seconds = -1.0").is_err());
+    assert!(Config::from_toml("[round]\nfinale_seconds = nan").is_err());
+    assert!(Config::from_toml("[round]\nfinale_seconds = 2.5").is_ok());
+}
+
+#[test]
+fn slow_motion_stretches_how_long_the_streamer_cant_be_hit() {
+    assert_eq!(count_hits_from_two_fires(1.0), 1);
+    assert_eq!(count_hits_from_two_fires(0.5), 1);
+}
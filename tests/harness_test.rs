@@ -0,0 +1,62 @@
+use get_the_streamer_game::{Config, GameObjectType, Harness, PlayerInput, RunningState};
+
+fn get_player_x(harness: &Harness) -> f32 {
+    harness
+        .get_game_objects()
+        .iter()
+        .find(|game_object| game_object.my_type == GameObjectType::Player)
+        .unwrap()
+        .location
+        .x
+}
+
+#[test]
+fn dropped_objects_show_up_in_the_arena() {
+    let mut harness = Harness::started(Config::default(), 1);
+    harness.chat("alice", "#fire 5");
+    harness.chat("bob", "#sword 8");
+    harness.run_ticks(1);
+
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 2);
+    assert_eq!(harness.get_score("alice"), 1);
+    assert_eq!(harness.get_score("bob"), 1);
+}
+
+#[test]
+fn holding_right_moves_the_streamer_right() {
+    let mut harness = Harness::started(Config::default(), 1);
+    let starting_x = get_player_x(&harness);
+    harness.set_input(PlayerInput {
+        right: true,
+        ..PlayerInput::default()
+    });
+    harness.run_ticks(30);
+
+    assert!(get_player_x(&harness) > starting_x);
+}
+
+#[test]
+fn chat_wins_once_the_streamer_is_out_of_lives() {
+    let mut harness = Harness::started(Config::default(), 1);
+    harness.chat("alice", "#fire 1");
+    harness.finish_round();
+
+    assert_eq!(harness.get_player_lives_left(), 0);
+    assert_eq!(harness.get_running_state(), RunningState::ChatWon);
+    assert!(harness
+        .get_chat_log()
+        .contains(&"Hit! alice gets 10 points"));
+}
+
+#[test]
+fn the_streamer_wins_when_time_runs_out() {
+    let config = Config::default();
+    let round_ticks = config.ticks_in(config.round.splash_duration() + config.round.game_time());
+    let mut harness = Harness::new(config, 1);
+    harness.run_ticks(round_ticks - 1);
+    assert_eq!(harness.get_running_state(), RunningState::Playing);
+
+    harness.run_ticks(2);
+    assert_eq!(harness.get_running_state(), RunningState::PlayerWon);
+    assert_eq!(harness.get_player_lives_left(), 3);
+}
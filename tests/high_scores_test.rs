@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use get_the_streamer_game::{HighScores, Score};
+
+/// A fresh folder for each test to keep its high scores in
+fn create_folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!(
+        "get_the_streamer_high_scores_{}_{}",
+        name,
+        std::process::id()
+    ));
+    fs::remove_dir_all(&folder).ok();
+    fs::create_dir_all(&folder).unwrap();
+    folder
+}
+
+/// Scores keyed by user id, like the ones from a round
+fn scores(scores: &[(&str, &str, u128)]) -> HashMap<String, Score> {
+    scores
+        .iter()
+        .map(|(user_id, name, points)| {
+            (
+                (*user_id).to_owned(),
+                Score::new((*name).to_owned(), *points),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn scores_are_added_up_between_rounds() {
+    let folder = create_folder("added_up");
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "alice", 11), ("2", "bob", 1)]));
+    high_scores.save().unwrap();
+
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "alice", 2)]));
+
+    assert_eq!(
+        high_scores.get_scores(),
+        &scores(&[("1", "alice", 13), ("2", "bob", 1)])
+    );
+    assert!(high_scores.get_warnings().is_empty());
+}
+
+#[test]
+fn changing_display_name_keeps_the_score() {
+    let folder = create_folder("display_name");
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "alice", 11)]));
+    high_scores.add_scores(&scores(&[("1", "Alice_the_Great", 1)]));
+
+    assert_eq!(
+        high_scores.get_scores(),
+        &scores(&[("1", "Alice_the_Great", 12)])
+    );
+}
+
+#[test]
+fn the_old_format_is_migrated_once_chatters_play_again() {
+    let folder = create_folder("migrated");
+    fs::write(folder.join("high_scores"), "alice:12\r\nbob:3\r\n").unwrap();
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "Alice", 1)]));
+    high_scores.save().unwrap();
+
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("2", "bob", 1)]));
+    assert_eq!(
+        high_scores.get_scores(),
+        &scores(&[("1", "Alice", 13), ("2", "bob", 4)])
+    );
+    assert!(folder.join("high_scores.json").exists());
+}
+
+#[test]
+fn broken_lines_in_the_old_format_are_skipped() {
+    let folder = create_folder("broken_legacy");
+    fs::write(
+        folder.join("high_scores"),
+        "alice:12\r\nbob\r\ncarol:lots\r\n",
+    )
+    .unwrap();
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "alice", 0), ("3", "carol", 0)]));
+
+    assert_eq!(
+        high_scores.get_scores(),
+        &scores(&[("1", "alice", 12), ("3", "carol", 0)])
+    );
+    assert_eq!(high_scores.get_warnings().len(), 2);
+    assert!(high_scores.get_warnings()[0].contains("line 2"));
+}
+
+#[test]
+fn name_keyed_entries_are_merged() {
+    let folder = create_folder("name_keyed");
+    let high_scores_file = r#"{
+        "version": 1,
+        "scores": [
+            { "name": "alice", "points": 12 },
+            { "name": "ALICE", "points": 3 },
+            { "name": "bob" },
+            { "name": "carol", "points": 5 }
+        ]
+    }"#;
+    fs::write(folder.join("high_scores.json"), high_scores_file).unwrap();
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "Alice", 1), ("3", "carol", 0)]));
+
+    assert_eq!(
+        high_scores.get_scores(),
+        &scores(&[("1", "Alice", 16), ("3", "carol", 5)])
+    );
+    assert_eq!(high_scores.get_warnings().len(), 1);
+    assert!(high_scores.get_warnings()[0].contains("entry 3"));
+}
+
+#[test]
+fn the_backup_is_used_when_the_file_is_broken() {
+    let folder = create_folder("backup");
+    let mut high_scores = HighScores::load(&folder).unwrap();
+    high_scores.add_scores(&scores(&[("1", "alice", 12)]));
+    high_scores.save().unwrap();
+    high_scores.add_scores(&scores(&[("1", "alice", 1)]));
+    high_scores.save().unwrap();
+    fs::write(folder.join("high_scores.json"), "{ \"version\": 2, \"sco").unwrap();
+
+    let high_scores = HighScores::load(&folder).unwrap();
+    assert_eq!(high_scores.get_scores(), &scores(&[("1", "alice", 12)]));
+    assert_eq!(high_scores.get_warnings().len(), 1);
+}
+
+#[test]
+fn scores_from_a_newer_version_are_not_loaded() {
+    let folder = create_folder("newer_with_backup");
+    fs::write(
+        folder.join("high_scores.json"),
+        r#"{ "version": 99, "scores": [] }"#,
+    )
+    .unwrap();
+    fs::write(
+        folder.join("high_scores.json.bak"),
+        r#"{ "version": 2, "scores": [] }"#,
+    )
+    .unwrap();
+
+    assert!(HighScores::load(&folder).is_err());
+}
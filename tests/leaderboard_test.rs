@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use get_the_streamer_game::{Config, Harness, Score};
+
+fn create_high_scores() -> HashMap<String, Score> {
+    let mut high_scores = HashMap::new();
+    high_scores.insert("alice".to_owned(), Score::new("alice".to_owned(), 40));
+    high_scores.insert("bob".to_owned(), Score::new("bob".to_owned(), 25));
+    high_scores
+}
+
+fn last_chat_message(harness: &Harness) -> &str {
+    harness.get_chat_log().last().copied().unwrap_or_default()
+}
+
+#[test]
+fn the_leaderboard_can_be_asked_for_before_the_round_starts() {
+    let mut harness = Harness::new(Config::default(), 1);
+    harness.set_high_scores(create_high_scores());
+    harness.chat("carol", "!top");
+    harness.run_ticks(1);
+
+    assert_eq!(
+        last_chat_message(&harness),
+        "Top 2: 1. alice (40), 2. bob (25)"
+    );
+}
+
+#[test]
+fn answers_count_this_rounds_points() {
+    let mut harness = Harness::started(Config::default(), 1);
+    harness.set_high_scores(create_high_scores());
+    harness.chat("bob", "#fire 5");
+    harness.run_ticks(180);
+    harness.chat("bob", "!score");
+    harness.run_ticks(1);
+    assert_eq!(
+        last_chat_message(&harness),
+        "bob has 1 points this round and 26 in total"
+    );
+
+    harness.run_ticks(180);
+    harness.chat("bob", "!rank");
+    harness.run_ticks(1);
+    assert_eq!(
+        last_chat_message(&harness),
+        "bob is ranked 2 of 2 with 26 points"
+    );
+}
+
+#[test]
+fn answers_are_rate_limited() {
+    let config = Config::default();
+    let interval = config.ticks_in(config.chat.leaderboard_interval());
+    let cooldown = config.ticks_in(config.chat.leaderboard_cooldown());
+    let mut harness = Harness::new(Config::default(), 1);
+    harness.set_high_scores(create_high_scores());
+    let answers = |harness: &Harness| {
+        harness
+            .get_chat_log()
+            .iter()
+            .filter(|message| message.contains("in total"))
+            .count()
+    };
+
+    harness.chat("alice", "!score");
+    harness.chat("bob", "!score");
+    harness.run_ticks(interval);
+    assert_eq!(answers(&harness), 1);
+
+    harness.chat("alice", "!score");
+    harness.chat("bob", "!score");
+    harness.run_ticks(1);
+    assert_eq!(answers(&harness), 2);
+    assert_eq!(
+        last_chat_message(&harness),
+        "bob has 0 points this round and 25 in total"
+    );
+
+    harness.run_ticks(cooldown);
+    harness.chat("alice", "!score");
+    harness.run_ticks(1);
+    assert_eq!(answers(&harness), 3);
+}
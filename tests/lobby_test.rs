@@ -0,0 +1,94 @@
+use get_the_streamer_game::{Config, GameObjectType, Harness, RunningState};
+
+#[test]
+fn chatters_can_join_and_drop_early_during_the_splash() {
+    let mut harness = Harness::new(Config::default(), 1);
+    harness.chat("alice", "#join");
+    harness.chat("bob", "#fire 3");
+    harness.chat("bob", "#sword 4");
+    harness.chat("carol", "#fire 5 #sword 6");
+    harness.run_ticks(1);
+
+    let players: Vec<&str> = harness
+        .get_simulation()
+        .get_lobby_players()
+        .iter()
+        .map(|player| player.name.as_str())
+        .collect();
+    assert_eq!(players, vec!["alice", "bob", "carol"]);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 0);
+    assert!(harness
+        .get_chat_log()
+        .contains(&"Only bob's first message of drops counts before the round starts"));
+
+    harness.start_round();
+    harness.run_ticks(5);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 3);
+    assert_eq!(harness.get_score("bob"), 1);
+    assert_eq!(harness.get_score("carol"), 2);
+    assert!(harness.get_simulation().get_lobby_players().is_empty());
+}
+
+#[test]
+fn the_round_waits_for_enough_players() {
+    let mut config = Config::default();
+    config.round.splash_duration_seconds = 1;
+    config.round.min_players = 2;
+    let mut harness = Harness::new(config, 1);
+    harness.chat("alice", "#join");
+    harness.run_ticks(60 * 5);
+
+    assert_eq!(harness.get_running_state(), RunningState::StartingSoon);
+    assert_eq!(harness.get_simulation().get_players_needed(), 1);
+    assert_eq!(
+        harness
+            .get_chat_log()
+            .iter()
+            .filter(|message| **message == "Waiting for 1 more player, type #join to play")
+            .count(),
+        1
+    );
+
+    harness.chat("bob", "#JOIN");
+    harness.run_ticks(1);
+    assert_eq!(harness.get_running_state(), RunningState::Playing);
+}
+
+#[test]
+fn early_drops_are_held_to_the_drop_limits() {
+    let mut config = Config::default();
+    config.drops.per_round = 1;
+    let mut harness = Harness::new(config, 1);
+    harness.chat("alice", "#fire 3 #sword 4");
+    harness.start_round();
+    harness.run_ticks(1);
+
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 1);
+    harness.chat("alice", "#fire 5");
+    harness.run_ticks(1);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 1);
+}
+
+#[test]
+fn a_big_lobby_gets_every_early_drop() {
+    let config = Config::default();
+    let chatters = config.drops.max_per_second * 2;
+    let mut harness = Harness::new(config, 1);
+    for chatter in 0..chatters {
+        harness.chat(
+            &format!("chatter{}", chatter),
+            &format!("#fire {}", chatter),
+        );
+    }
+    harness.start_round();
+    harness.run_ticks(10);
+
+    assert_eq!(
+        harness.count_game_objects(GameObjectType::Enemy),
+        chatters as usize
+    );
+    assert!(!harness
+        .get_chat_log()
+        .iter()
+        .any(|message| message.starts_with("Chat is dropping too fast")));
+}
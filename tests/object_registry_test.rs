@@ -0,0 +1,120 @@
+use get_the_streamer_game::{Config, GameObjectType, Harness, ObjectRegistry};
+use rand::{rngs::StdRng, SeedableRng};
+
+const ROCK: &str = r##"
+[[objects]]
+name = "rock"
+commands = ["#rock", "#boulder"]
+sprite = "/rock.png"
+width = 60.0
+height = 60.0
+behavior = "fire"
+lifetime_seconds = 10.0
+damage = 2
+
+[[objects]]
+name = "heart"
+commands = ["#heart"]
+sprite = "/heart.png"
+width = 48.0
+height = 48.0
+behavior = "heart"
+random_weight = 0
+"##;
+
+fn create_harness(objects: ObjectRegistry) -> Harness {
+    let config = Config {
+        objects,
+        ..Config::default()
+    };
+    Harness::started(config, 1)
+}
+
+fn get_player_column(harness: &Harness) -> usize {
+    let player = harness
+        .get_game_objects()
+        .iter()
+        .find(|game_object| game_object.my_type == GameObjectType::Player)
+        .unwrap();
+    let center_x = player.location.x + player.location.w / 2.0;
+    let column_width = harness
+        .get_simulation()
+        .get_column_coordinates_by_index(1)
+        .x
+        - harness
+            .get_simulation()
+            .get_column_coordinates_by_index(0)
+            .x;
+    (center_x / column_width) as usize
+}
+
+#[test]
+fn the_objects_the_game_comes_with_load() {
+    let objects = ObjectRegistry::default();
+
+    assert_eq!(objects.get_objects().len(), 4);
+    assert_eq!(objects.find_by_command("#snek").unwrap().name, "snake");
+}
+
+#[test]
+fn new_objects_can_be_dropped_without_code_changes() {
+    let mut harness = create_harness(ObjectRegistry::from_toml(ROCK).unwrap());
+    let column = get_player_column(&harness);
+    harness.chat("alice", &format!("#boulder {}", column));
+    harness.run_ticks(120);
+
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 1);
+    assert_eq!(harness.get_player_lives_left(), 1);
+    assert_eq!(harness.get_score("alice"), 11);
+
+    harness.chat("bob", "#fire 2");
+    harness.run_ticks(1);
+    assert_eq!(harness.count_game_objects(GameObjectType::Enemy), 1);
+}
+
+#[test]
+fn random_drops_skip_objects_without_a_weight() {
+    let objects = ObjectRegistry::from_toml(ROCK).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    assert!((0..100).all(|_| objects.choose_random(&mut rng).name == "rock"));
+}
+
+#[test]
+fn bad_objects_name_the_problem() {
+    let no_lifetime = ROCK.replace("lifetime_seconds = 10.0\n", "");
+    let error = ObjectRegistry::from_toml(&no_lifetime).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("`rock` needs a `lifetime_seconds`"));
+
+    let endless_lifetime = ROCK.replace("lifetime_seconds = 10.0", "lifetime_seconds = inf");
+    assert!(ObjectRegistry::from_toml(&endless_lifetime).is_err());
+
+    let harmless = ROCK.replace("damage = 2", "damage = 0");
+    let error = ObjectRegistry::from_toml(&harmless).unwrap_err();
+    assert!(error.to_string().contains("`rock` needs `damage` above 0"));
+
+    let same_command = ROCK.replace("\"#heart\"", "\"#ROCK\"");
+    let error = ObjectRegistry::from_toml(&same_command).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("more than one object uses `#ROCK`"));
+
+    let random_command = ROCK.replace("\"#heart\"", "\"#random\"");
+    assert!(ObjectRegistry::from_toml(&random_command).is_err());
+}
+
+#[test]
+fn the_sidebar_lists_every_command_by_what_it_does() {
+    let objects = ObjectRegistry::default();
+    let command_help = objects.get_command_help();
+    let commands: Vec<&str> = command_help.iter().map(|help| help.command).collect();
+
+    assert_eq!(commands, ["#fire", "#sword", "#snake", "#random", "#heart"]);
+    assert_eq!(command_help[2].aliases, ["#snek"]);
+    assert_eq!(command_help[2].description, "slithers after the streamer");
+    assert!(command_help[3].object.is_none());
+    assert!(command_help[4].helps_streamer);
+    assert!(!command_help[0].helps_streamer);
+}
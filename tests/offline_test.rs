@@ -1,22 +1,29 @@
-use get_the_streamer_game::GameState;
+use get_the_streamer_game::{
+    Config, FakeIrcServer, GameState, TwitchChatBackend, TwitchSettings, WINDOW_SIZE,
+};
 use ggez::conf::{WindowMode, WindowSetup};
 use ggez::{event, ContextBuilder};
-use std::sync::mpsc::channel;
 use std::thread;
-use twitch_chat_wrapper::chat_message::ChatMessage;
 mod chat_test_mock;
 
-const WINDOW_SIZE: (f32, f32) = (1920.0, 1080.0);
-
+/// Opens a window and plays against a busy chat until the window is closed,
+/// run it with `cargo test -- --ignored`
 #[test]
+#[ignore]
 fn main() {
-    let (send_to_game, receive_from_twitch) = channel::<ChatMessage>();
-    let (send_to_twitch, _receive_from_game) = channel::<String>();
+    let server = FakeIrcServer::start("test_streamer").unwrap();
+    let settings = TwitchSettings {
+        name: "test_streamer".to_owned(),
+        token: "oauth:test".to_owned(),
+        channel: "test_streamer".to_owned(),
+        address: server.get_address(),
+    };
+    let chat_backend = TwitchChatBackend::connect(&settings).unwrap();
 
     chat_test_mock::run(
-        send_to_game,
+        server,
         5,
-        get_the_streamer_game::SPLASH_DURATION,
+        Config::default().round.splash_duration(),
         250,
         1500,
     );
@@ -32,8 +39,14 @@ fn main() {
                 Err(error) => panic!(error),
             };
 
-        let game_state =
-            &mut GameState::new(send_to_twitch, receive_from_twitch, WINDOW_SIZE, context).unwrap();
+        let game_state = &mut GameState::new(
+            Box::new(chat_backend),
+            WINDOW_SIZE,
+            Config::default(),
+            rand::random(),
+            context,
+        )
+        .unwrap();
         match event::run(context, event_loop, game_state) {
             Ok(_) => println!("Thanks for playing!"),
             Err(error) => println!("Error occurred: {}", error),
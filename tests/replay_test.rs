@@ -0,0 +1,85 @@
+use get_the_streamer_game::{
+    get_arena_size, Chatter, Config, PlayerInput, Replay, ReplayRecorder, Simulation, WINDOW_SIZE,
+};
+
+fn get_object_locations(simulation: &Simulation) -> Vec<(f32, f32)> {
+    simulation
+        .get_game_objects()
+        .iter()
+        .map(|game_object| (game_object.location.x, game_object.location.y))
+        .collect()
+}
+
+#[test]
+fn a_recorded_round_plays_back_the_same() {
+    let mut recording = vec![];
+    let mut recorder = ReplayRecorder::new(&mut recording, 7).unwrap();
+    let mut simulation = Simulation::new(get_arena_size(WINDOW_SIZE), None, Config::default(), 7);
+    let config = Config::default();
+    let ticks = config.ticks_in(config.round.splash_duration()) + 120;
+
+    for tick in 0..ticks {
+        if tick % 20 == 0 {
+            let chatter = Chatter::new(format!("chatter{}", tick % 3), (10, 20, 30), true);
+            recorder
+                .record_chat_message(simulation.get_ticks(), &chatter, "#random")
+                .unwrap();
+            simulation.handle_chat_message("#random", chatter);
+        }
+        let input = PlayerInput {
+            left: tick % 50 < 25,
+            right: tick % 50 >= 25,
+            jump: tick % 30 == 0,
+        };
+        recorder
+            .record_input(simulation.get_ticks(), &input)
+            .unwrap();
+        simulation.update(&input);
+    }
+
+    let mut replay = Replay::load(recording.as_slice()).unwrap();
+    let mut replayed_simulation = Simulation::new(
+        get_arena_size(WINDOW_SIZE),
+        None,
+        Config::default(),
+        replay.seed,
+    );
+    while !replay.is_finished() {
+        let tick = replayed_simulation.get_ticks();
+        for (chatter, message) in replay.take_chat_messages(tick) {
+            replayed_simulation.handle_chat_message(&message, chatter);
+        }
+        let input = replay.take_input(tick);
+        replayed_simulation.update(&input);
+    }
+
+    assert_eq!(replayed_simulation.get_ticks(), simulation.get_ticks());
+    assert_eq!(replayed_simulation.get_scores(), simulation.get_scores());
+    assert_eq!(
+        get_object_locations(&replayed_simulation),
+        get_object_locations(&simulation)
+    );
+}
+
+#[test]
+fn loading_a_replay_reports_the_bad_line() {
+    let replay = "seed 3\ninput 0 0 1 0\ninput one 0 0 0\n";
+    let error = Replay::load(replay.as_bytes()).unwrap_err();
+
+    assert!(error.to_string().contains("line 3"));
+}
+
+#[test]
+fn pauses_are_played_back_on_the_tick_they_happened() {
+    let mut recording = vec![];
+    let mut recorder = ReplayRecorder::new(&mut recording, 3).unwrap();
+    recorder.record_pause(20, true).unwrap();
+    recorder.record_pause(50, false).unwrap();
+
+    let mut replay = Replay::load(recording.as_slice()).unwrap();
+    assert!(replay.take_pauses(19).is_empty());
+    assert_eq!(replay.take_pauses(20), vec![true]);
+    assert!(!replay.is_finished());
+    assert_eq!(replay.take_pauses(60), vec![false]);
+    assert!(replay.is_finished());
+}
@@ -0,0 +1,40 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+
+use get_the_streamer_game::{Config, Scenario};
+
+#[test]
+fn every_scenario_plays_out_as_expected() {
+    let mut failures = vec![];
+    for entry in fs::read_dir("tests/scenarios").unwrap() {
+        let path = entry.unwrap().path();
+        let scenario = Scenario::load(BufReader::new(File::open(&path).unwrap())).unwrap();
+        if let Err(error) = scenario.run(Config::default()) {
+            failures.push(format!("{}\n{}", path.display(), error));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
+
+#[test]
+fn unmet_expectations_are_reported_with_their_line() {
+    let scenario = "seed 1\nt=1.0 alice #fire 5\nt=2.0 expect score alice 5\n";
+    let error = Scenario::load(scenario.as_bytes())
+        .unwrap()
+        .run(Config::default())
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "line 3: expected alice to have 5 points, but they have 1"
+    );
+}
+
+#[test]
+fn loading_a_scenario_reports_the_bad_line() {
+    let scenario = "seed 1\nt=1.0 alice #fire 5\nt=2.0 press up\n";
+    let error = Scenario::load(scenario.as_bytes()).unwrap_err();
+
+    assert!(error.to_string().starts_with("line 3:"));
+}
@@ -0,0 +1,71 @@
+use get_the_streamer_game::{Config, Harness, RunningState};
+
+fn create_harness(rounds: u32) -> Harness {
+    let mut config = Config::default();
+    config.round.game_time_seconds = 5;
+    config.round.splash_duration_seconds = 1;
+    config.session.rounds = rounds;
+    config.session.break_seconds = 2;
+    Harness::new(config, 1)
+}
+
+fn get_session_points(harness: &Harness, user_id: &str) -> u128 {
+    harness
+        .get_simulation()
+        .get_session_scores()
+        .get(user_id)
+        .map(|score| score.points)
+        .unwrap_or(0)
+}
+
+#[test]
+fn the_next_round_starts_after_the_break() {
+    let mut harness = create_harness(3);
+    harness.start_round();
+    harness.chat("alice", "#fire 5");
+    harness.finish_round();
+    assert_eq!(harness.get_score("alice"), 1);
+
+    harness.run_ticks(60);
+    assert_eq!(harness.get_running_state(), RunningState::PlayerWon);
+    harness.start_round();
+    assert_eq!(harness.get_simulation().get_round(), 2);
+    assert_eq!(harness.get_score("alice"), 0);
+    assert_eq!(harness.get_player_lives_left(), 3);
+
+    harness.chat("alice", "#fire 5");
+    harness.finish_round();
+    assert_eq!(get_session_points(&harness, "alice"), 2);
+    assert!(harness
+        .get_chat_log()
+        .contains(&"After 2 rounds alice leads the session with 2 points"));
+}
+
+#[test]
+fn the_session_ends_after_the_last_round() {
+    let mut harness = create_harness(2);
+    for _ in 0..2 {
+        harness.start_round();
+        harness.finish_round();
+    }
+    harness.run_ticks(60 * 10);
+
+    assert!(harness.get_simulation().is_session_over());
+    assert_eq!(harness.get_simulation().get_round(), 2);
+    assert!(harness.get_running_state().is_game_over());
+    assert!(harness
+        .get_chat_log()
+        .contains(&"That was the last round, thanks for playing!"));
+}
+
+#[test]
+fn sessions_without_a_round_limit_keep_going() {
+    let mut harness = create_harness(0);
+    for _ in 0..5 {
+        harness.start_round();
+        harness.finish_round();
+    }
+
+    assert!(!harness.get_simulation().is_session_over());
+    assert_eq!(harness.get_simulation().get_round(), 5);
+}
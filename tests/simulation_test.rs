@@ -0,0 +1,97 @@
+use get_the_streamer_game::{
+    get_arena_size, Chatter, Config, GameEvent, PlayerInput, RunningState, Simulation, WINDOW_SIZE,
+};
+
+const SEED: u64 = 42;
+
+fn start_round(simulation: &mut Simulation) {
+    let config = Config::default();
+    let splash_ticks = config.ticks_in(config.round.splash_duration());
+    for _ in 0..splash_ticks {
+        simulation.update(&PlayerInput::default());
+    }
+}
+
+#[test]
+fn round_starts_once_the_splash_is_done() {
+    let mut simulation =
+        Simulation::new(get_arena_size(WINDOW_SIZE), None, Config::default(), SEED);
+    simulation.update(&PlayerInput::default());
+    assert_eq!(simulation.get_running_state(), RunningState::StartingSoon);
+
+    start_round(&mut simulation);
+    assert_eq!(simulation.get_running_state(), RunningState::Playing);
+}
+
+#[test]
+fn chat_is_ignored_before_the_round_starts() {
+    let mut simulation =
+        Simulation::new(get_arena_size(WINDOW_SIZE), None, Config::default(), SEED);
+    simulation.handle_chat_message(
+        "#fire 3",
+        Chatter::new("alice".to_owned(), (0, 0, 0), false),
+    );
+
+    assert_eq!(simulation.get_game_objects().len(), 1);
+    assert!(simulation.get_scores().is_empty());
+}
+
+#[test]
+fn dropping_an_object_scores_a_point() {
+    let mut simulation =
+        Simulation::new(get_arena_size(WINDOW_SIZE), None, Config::default(), SEED);
+    start_round(&mut simulation);
+
+    let alice = Chatter::new("alice".to_owned(), (0, 0, 0), false);
+    simulation.handle_chat_message("#fire 3", alice.clone());
+    let events = simulation.update(&PlayerInput::default());
+
+    assert!(events.contains(&GameEvent::ObjectDropped(alice)));
+    assert_eq!(simulation.get_game_objects().len(), 2);
+    assert_eq!(simulation.get_scores()["alice"].points, 1);
+}
+
+#[test]
+fn the_same_seed_plays_the_same_round() {
+    let play_round = || {
+        let mut simulation =
+            Simulation::new(get_arena_size(WINDOW_SIZE), None, Config::default(), SEED);
+        start_round(&mut simulation);
+        for _ in 0..10 {
+            let chatter = Chatter::new("alice".to_owned(), (0, 0, 0), false);
+            simulation.handle_chat_message("#random", chatter);
+            simulation.update(&PlayerInput::default());
+        }
+        simulation
+            .get_game_objects()
+            .iter()
+            .map(|game_object| (game_object.location.x, game_object.location.y))
+            .collect::<Vec<(f32, f32)>>()
+    };
+
+    assert_eq!(play_round(), play_round());
+}
+
+#[test]
+fn config_errors_name_the_bad_key() {
+    let error = Config::from_toml("[player]\nfriction = -1.0\n").unwrap_err();
+    assert!(error.to_string().contains("player.friction"));
+
+    let error = Config::from_toml("[round]\nlives = \"lots\"\n").unwrap_err();
+    assert!(error.to_string().contains("round.lives"));
+}
+
+#[test]
+fn config_keys_left_out_keep_their_defaults() {
+    let config = Config::from_toml("[round]\ngame_time_seconds = 30\n").unwrap();
+
+    assert_eq!(config.round.game_time_seconds, 30);
+    assert_eq!(config.round.lives, Config::default().round.lives);
+}
+
+#[test]
+fn the_bundled_config_matches_the_defaults() {
+    let config = Config::from_toml(include_str!("../assets/config.toml")).unwrap();
+
+    assert_eq!(config, Config::default());
+}
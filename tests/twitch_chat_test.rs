@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant};
+
+use get_the_streamer_game::{
+    get_arena_size, ChatBackend, ChatMessage, Chatter, ChatterRole, Config, FakeIrcServer,
+    GameEvent, PlayerInput, Simulation, TwitchChatBackend, TwitchSettings, WINDOW_SIZE,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn connect() -> (FakeIrcServer, TwitchChatBackend) {
+    let server = FakeIrcServer::start("streamer").unwrap();
+    let chat_backend = connect_to(&server);
+    (server, chat_backend)
+}
+
+fn connect_to(server: &FakeIrcServer) -> TwitchChatBackend {
+    let settings = TwitchSettings {
+        name: "Streamer".to_owned(),
+        token: "oauth:secret".to_owned(),
+        channel: "streamer".to_owned(),
+        address: server.get_address(),
+    };
+    TwitchChatBackend::connect(&settings).unwrap()
+}
+
+fn receive(chat_backend: &mut TwitchChatBackend) -> ChatMessage {
+    let started_at = Instant::now();
+    loop {
+        if let Some(chat_message) = chat_backend.receive() {
+            return chat_message;
+        }
+        assert!(started_at.elapsed() < TIMEOUT, "no chat message arrived");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn logs_in_and_joins_the_channel() {
+    let (server, _chat_backend) = connect();
+    let lines: Vec<String> = (0..4).filter_map(|_| server.next_line(TIMEOUT)).collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            "CAP REQ :twitch.tv/tags",
+            "PASS oauth:secret",
+            "NICK streamer",
+            "JOIN #streamer"
+        ]
+    );
+}
+
+#[test]
+fn viewers_messages_reach_the_game_with_their_tags() {
+    let (server, mut chat_backend) = connect();
+    let alice = Chatter::new("Alice".to_owned(), (255, 0, 16), true);
+    server.send_chat_message(&alice, "#fire 3");
+
+    assert_eq!(
+        receive(&mut chat_backend),
+        ChatMessage::new(alice, "#fire 3".to_owned())
+    );
+}
+
+#[test]
+fn escaped_tags_reach_the_game_unescaped() {
+    let (server, mut chat_backend) = connect();
+    let chatter = Chatter::new("Semi;Colon\\".to_owned(), (0, 0, 0), false)
+        .with_user_id("id with spaces".to_owned());
+    server.send_chat_message(&chatter, "#fire 3");
+
+    assert_eq!(receive(&mut chat_backend).chatter, chatter);
+}
+
+#[test]
+fn every_client_that_connects_hears_chat() {
+    let (server, mut chat_backend) = connect();
+    let mut other_chat_backend = connect_to(&server);
+    // both have logged in once the server has seen both joins
+    let joins = (0..8)
+        .filter_map(|_| server.next_line(TIMEOUT))
+        .filter(|line| line.starts_with("JOIN"))
+        .count();
+    assert_eq!(joins, 2);
+    let alice = Chatter::new("Alice".to_owned(), (255, 0, 16), false);
+    server.send_chat_message(&alice, "#fire 3");
+
+    assert_eq!(receive(&mut chat_backend).message, "#fire 3");
+    assert_eq!(receive(&mut other_chat_backend).message, "#fire 3");
+}
+
+#[test]
+fn moderators_and_the_streamer_are_told_apart_by_their_badges() {
+    let (server, mut chat_backend) = connect();
+    for role in &[
+        ChatterRole::Viewer,
+        ChatterRole::Moderator,
+        ChatterRole::Broadcaster,
+    ] {
+        let chatter = Chatter::new("Alice".to_owned(), (255, 0, 16), false).with_role(*role);
+        server.send_chat_message(&chatter, "!gts pause");
+
+        assert_eq!(receive(&mut chat_backend).chatter.role, *role);
+    }
+}
+
+#[test]
+fn answers_pings() {
+    let (server, _chat_backend) = connect();
+    server.ping();
+
+    let pong = (0..10).find_map(|_| {
+        server
+            .next_line(TIMEOUT)
+            .filter(|line| line.starts_with("PONG"))
+    });
+    assert_eq!(pong.as_deref(), Some("PONG :tmi.twitch.tv"));
+}
+
+#[test]
+fn a_round_can_be_played_through_chat() {
+    let (server, mut chat_backend) = connect();
+    let config = Config::default();
+    let mut simulation = Simulation::new(get_arena_size(WINDOW_SIZE), None, config.clone(), 1);
+    let mut events = vec![];
+    for _ in 0..=config.ticks_in(config.round.splash_duration()) {
+        events.extend(simulation.update(&PlayerInput::default()));
+    }
+
+    server.send_chat_message(
+        &Chatter::new("bob".to_owned(), (0, 0, 0), false),
+        "#sword 2",
+    );
+    let chat_message = receive(&mut chat_backend);
+    simulation.handle_chat_message(&chat_message.message, chat_message.chatter);
+    events.extend(simulation.update(&PlayerInput::default()));
+    for event in events {
+        if let GameEvent::SendToChat(message) = event {
+            chat_backend.send(message).unwrap();
+        }
+    }
+
+    assert_eq!(simulation.get_scores()["bob"].points, 1);
+    assert!(server
+        .next_chat_message(TIMEOUT)
+        .unwrap()
+        .starts_with("In 15 seconds"));
+    assert!(server
+        .next_chat_message(TIMEOUT)
+        .unwrap()
+        .starts_with("You have 120 seconds"));
+}